@@ -0,0 +1,267 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ self, TokenAccount, Transfer };
+
+use crate::{
+    errors::RewardError,
+    pool::{ collect_protocol_cut, ProtocolCutAccounts },
+    staking::{ distribute_delegator_share, DelegatorShareAccounts },
+    state::{
+        AdminAccount,
+        AutoCompound,
+        BlacklistEntry,
+        ClaimRole,
+        NfNodeEntry,
+        PayoutConfig,
+        RewardEntry,
+        RewardPool,
+        PAUSE_CLAIMS,
+    },
+};
+// a reward claimed for one role on one nfnode
+pub struct Claim {
+    pub role: ClaimRole,
+    pub beneficiary: Pubkey,
+    pub reward_amount: u64,
+    // paid to the cranker out of the reward, zero outside crank claims
+    pub crank_fee: u64,
+    // only the owner's deposit is compounded
+    pub auto_compound: Option<AutoCompound>,
+    pub deposit_requirement: u64,
+    pub now: i64,
+}
+
+// token accounts a claim is paid out through
+pub struct ClaimAccounts<'a, 'info> {
+    pub token_program: AccountInfo<'info>,
+    pub token_mint: AccountInfo<'info>,
+    pub token_storage_account: &'a Account<'info, TokenAccount>,
+    pub token_storage_authority: AccountInfo<'info>,
+    pub treasury_token_account: Option<AccountInfo<'info>>,
+    pub payout_config: AccountInfo<'info>,
+    // paid when the role has no payout destination
+    pub beneficiary_token_account: AccountInfo<'info>,
+    pub recipient_token_account: Option<&'a Account<'info, TokenAccount>>,
+    // blacklist entry of the wallet owning the recipient token account, required with it
+    pub recipient_blacklist_entry: Option<AccountInfo<'info>>,
+    // delegated stake on the nfnode, required for owner claims
+    pub nfnode_stake: Option<AccountInfo<'info>>,
+    pub stake_vault_account: Option<AccountInfo<'info>>,
+    pub deposit_storage_account: Option<AccountInfo<'info>>,
+    pub cranker_token_account: Option<AccountInfo<'info>>,
+}
+
+// checks every admin signed claim goes through, the caller checks the beneficiary holds the role
+pub fn require_claimable(
+    admin_account: &AdminAccount,
+    user_admin: &AccountInfo,
+    reward_entry: &RewardEntry,
+    nfnode_entry: &NfNodeEntry,
+    nonce: u64,
+    claim: &Claim,
+    blacklist_entry: &AccountInfo
+) -> Result<()> {
+    require!(
+        nfnode_entry.claim_deposit_covered(
+            &claim.role,
+            claim.auto_compound.as_ref(),
+            claim.deposit_requirement,
+            claim.now
+        ),
+        RewardError::DepositRequired
+    );
+    require!(!admin_account.is_paused(PAUSE_CLAIMS, claim.now), RewardError::ProgramPaused);
+    require!(
+        nonce > reward_entry.last_claimed_nonce ||
+            (reward_entry.last_claimed_nonce == 0 && nonce == 1) || // initialization
+            (reward_entry.last_claimed_nonce == u64::MAX && nonce == 1), // overflow unprobably
+        RewardError::NonceAlreadyClaimed
+    );
+    require!(user_admin.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    require!(user_admin.is_signer, RewardError::MissingAdminSignature);
+    msg!("Current timestamp: {}", claim.now);
+    // the cooldown runs per role, claiming as host does not block the manufacturer claim
+    require!(
+        admin_account.claim_period_elapsed(
+            nfnode_entry.role_last_claimed_timestamp(&claim.role),
+            claim.now
+        ),
+        RewardError::ClaimAlreadyMadeToday
+    );
+    require!(nfnode_entry.device_online(claim.now), RewardError::DeviceOffline);
+    require!(!nfnode_entry.frozen, RewardError::NfNodeFrozen);
+    require!(!nfnode_entry.unbonding(), RewardError::NfNodeUnbonding);
+    BlacklistEntry::require_not_blacklisted(blacklist_entry)
+}
+
+// records the claim on the reward entry and pays it. a short pool pays its pro-rata share above
+// its reserve, the rest is owed to the role on the reward entry
+pub fn settle_claim<'a, 'info>(
+    reward_pool: &mut RewardPool,
+    reward_entry: &mut RewardEntry,
+    nfnode_entry: &mut NfNodeEntry,
+    nonce: u64,
+    claim: Claim,
+    accounts: ClaimAccounts<'a, 'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<()> {
+    let reward_amount = reward_pool.record_shortfall(
+        reward_entry.owed_amount_mut(&claim.role),
+        claim.reward_amount,
+        accounts.token_storage_account.amount
+    )?;
+
+    reward_entry.version = RewardEntry::VERSION;
+    reward_entry.last_claimed_nonce = nonce;
+    reward_entry.last_claimed_timestamp = claim.now;
+    nfnode_entry.set_role_last_claimed_timestamp(&claim.role, claim.now);
+    pay_claim(reward_pool, nfnode_entry, Claim { reward_amount, ..claim }, accounts, signer_seeds)
+}
+
+// pays a claim the pool covers: routes it to the payout destination of the role, takes the
+// protocol cut, the delegator share and the crank fee, then escrows debt repayment and
+// auto-compounding into the deposit before paying out the rest
+pub fn pay_claim<'a, 'info>(
+    reward_pool: &mut RewardPool,
+    nfnode_entry: &mut NfNodeEntry,
+    claim: Claim,
+    accounts: ClaimAccounts<'a, 'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<()> {
+    nfnode_entry.total_rewards_claimed = nfnode_entry.total_rewards_claimed
+        .checked_add(claim.reward_amount)
+        .ok_or(RewardError::ArithmeticOverflow)?;
+
+    let payout_destination = PayoutConfig::destination_for(
+        &accounts.payout_config,
+        &claim.role,
+        &claim.beneficiary
+    )?;
+    let recipient_token_account_info = match payout_destination {
+        Some(destination) => {
+            let recipient_token_account = accounts.recipient_token_account.ok_or(
+                RewardError::InvalidPayoutDestination
+            )?;
+            require!(
+                recipient_token_account.key() == destination ||
+                    recipient_token_account.owner == destination,
+                RewardError::InvalidPayoutDestination
+            );
+            BlacklistEntry::require_wallet_not_blacklisted(
+                &recipient_token_account.owner,
+                accounts.recipient_blacklist_entry.as_ref()
+            )?;
+            recipient_token_account.to_account_info()
+        }
+        None => accounts.beneficiary_token_account,
+    };
+
+    // protocol fee and burn come off the top, the beneficiary is paid the rest
+    let reward_amount = collect_protocol_cut(
+        reward_pool,
+        claim.reward_amount,
+        ProtocolCutAccounts {
+            token_program: accounts.token_program.clone(),
+            token_mint: accounts.token_mint,
+            token_storage_account: accounts.token_storage_account.to_account_info(),
+            token_storage_authority: accounts.token_storage_authority.clone(),
+            treasury_token_account: accounts.treasury_token_account,
+        },
+        signer_seeds
+    )?;
+    // delegators staked on the nfnode take their share of the owner rewards
+    let reward_amount = match claim.role {
+        ClaimRole::Owner => reward_amount - distribute_delegator_share(
+            reward_amount,
+            nfnode_entry.deposit_amount,
+            DelegatorShareAccounts {
+                token_program: accounts.token_program.clone(),
+                token_storage_account: accounts.token_storage_account.to_account_info(),
+                token_storage_authority: accounts.token_storage_authority.clone(),
+                nfnode_stake: accounts.nfnode_stake.ok_or(ErrorCode::AccountNotEnoughKeys)?,
+                stake_vault_account: accounts.stake_vault_account,
+            },
+            signer_seeds
+        )?,
+        _ => reward_amount,
+    };
+    require!(
+        claim.crank_fee == 0 || claim.crank_fee < reward_amount,
+        RewardError::InvalidCrankFee
+    );
+    let reward_amount = reward_amount - claim.crank_fee;
+
+    // financed deposits are repaid from the owner's rewards first, auto-compounding applies to
+    // what is left
+    let withheld_amount = match claim.role {
+        ClaimRole::Owner => nfnode_entry.debt_withholding_amount(reward_amount)?,
+        _ => 0,
+    };
+    let compound_amount = match &claim.auto_compound {
+        Some(auto_compound) if nfnode_entry.deposit_compounds_for(&claim.beneficiary) =>
+            auto_compound.compound_amount(
+                reward_amount - withheld_amount,
+                nfnode_entry.deposit_amount + withheld_amount,
+                claim.deposit_requirement
+            )?,
+        _ => 0,
+    };
+    let escrow_amount = withheld_amount + compound_amount;
+
+    if escrow_amount > 0 {
+        let deposit_storage_account = accounts.deposit_storage_account.ok_or(
+            RewardError::DepositStorageRequired
+        )?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.clone(),
+                Transfer {
+                    from: accounts.token_storage_account.to_account_info(),
+                    to: deposit_storage_account,
+                    authority: accounts.token_storage_authority.clone(),
+                },
+                signer_seeds
+            ),
+            escrow_amount
+        )?;
+        if nfnode_entry.deposit_amount == 0 {
+            nfnode_entry.deposit_timestamp = claim.now;
+            nfnode_entry.depositor = claim.beneficiary;
+        }
+        nfnode_entry.deposit_amount = nfnode_entry.deposit_amount
+            .checked_add(escrow_amount)
+            .ok_or(RewardError::ArithmeticOverflow)?;
+        nfnode_entry.deposit_debt -= withheld_amount;
+    }
+    if reward_amount > escrow_amount {
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.clone(),
+                Transfer {
+                    from: accounts.token_storage_account.to_account_info(),
+                    to: recipient_token_account_info,
+                    authority: accounts.token_storage_authority.clone(),
+                },
+                signer_seeds
+            ),
+            reward_amount - escrow_amount
+        )?;
+    }
+    if claim.crank_fee > 0 {
+        let cranker_token_account = accounts.cranker_token_account.ok_or(
+            RewardError::InvalidCrankFee
+        )?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program,
+                Transfer {
+                    from: accounts.token_storage_account.to_account_info(),
+                    to: cranker_token_account,
+                    authority: accounts.token_storage_authority,
+                },
+                signer_seeds
+            ),
+            claim.crank_fee
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        extension::{
+            group_member_pointer::GroupMemberPointer,
+            metadata_pointer::MetadataPointer,
+            BaseStateWithExtensions,
+            StateWithExtensions,
+        },
+        state::Mint,
+    },
+    token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata,
+};
+use spl_token_group_interface::state::{ TokenGroup, TokenGroupMember };
+
+use crate::{ errors::RewardError, state::{ MintAuthorityEntry, NfNodeType } };
+// additional metadata key holding the device type of the nfnode
+pub const DEVICE_TYPE_METADATA_KEY: &str = "device_type";
+
+// the collection mint must carry the token-group extension
+pub fn verify_collection_group(collection_info: &AccountInfo) -> Result<()> {
+    require_keys_eq!(
+        *collection_info.owner,
+        anchor_spl::token_2022::ID,
+        RewardError::InvalidCollection
+    );
+    let collection_data = collection_info.try_borrow_data()?;
+    let collection_mint = StateWithExtensions::<Mint>::unpack(&collection_data).map_err(
+        |_| error!(RewardError::InvalidCollection)
+    )?;
+    let group = collection_mint
+        .get_extension::<TokenGroup>()
+        .map_err(|_| error!(RewardError::InvalidCollection))?;
+    require_keys_eq!(group.mint, collection_info.key(), RewardError::InvalidCollection);
+    Ok(())
+}
+
+// checks the nft is a member of the collection through its token-group-member extension and
+// that the device type in its embedded metadata matches the requested nfnode type
+pub fn verify_collection_member(
+    nft_mint_info: &AccountInfo,
+    collection: &Pubkey,
+    nfnode_type: &NfNodeType
+) -> Result<()> {
+    let nft_mint_data = nft_mint_info.try_borrow_data()?;
+    let nft_mint = StateWithExtensions::<Mint>::unpack(&nft_mint_data).map_err(
+        |_| error!(RewardError::InvalidNftMint)
+    )?;
+
+    let group_member_pointer = nft_mint
+        .get_extension::<GroupMemberPointer>()
+        .map_err(|_| error!(RewardError::NotCollectionMember))?;
+    require!(
+        Option::<Pubkey>::from(group_member_pointer.member_address) == Some(nft_mint_info.key()),
+        RewardError::NotCollectionMember
+    );
+    let group_member = nft_mint
+        .get_extension::<TokenGroupMember>()
+        .map_err(|_| error!(RewardError::NotCollectionMember))?;
+    require_keys_eq!(group_member.mint, nft_mint_info.key(), RewardError::NotCollectionMember);
+    require_keys_eq!(group_member.group, *collection, RewardError::NotCollectionMember);
+
+    let metadata_pointer = nft_mint
+        .get_extension::<MetadataPointer>()
+        .map_err(|_| error!(RewardError::InvalidNftMetadata))?;
+    require!(
+        Option::<Pubkey>::from(metadata_pointer.metadata_address) == Some(nft_mint_info.key()),
+        RewardError::InvalidNftMetadata
+    );
+    let metadata = nft_mint
+        .get_variable_len_extension::<TokenMetadata>()
+        .map_err(|_| error!(RewardError::InvalidNftMetadata))?;
+    require_keys_eq!(metadata.mint, nft_mint_info.key(), RewardError::InvalidNftMetadata);
+    let device_type = metadata.additional_metadata
+        .iter()
+        .find(|(key, _)| key == DEVICE_TYPE_METADATA_KEY)
+        .and_then(|(_, value)| NfNodeType::from_device_type(value))
+        .ok_or(RewardError::InvalidNftMetadata)?;
+    require!(device_type == *nfnode_type, RewardError::NfNodeTypeMismatch);
+    Ok(())
+}
+
+// with a collection set, verified membership stands in for the mint authority check so nfts whose
+// mint authority was revoked keep working, any other nft needs the entry of its mint authority
+pub fn verify_nfnode_mint(
+    nft_mint_info: &AccountInfo,
+    collection: &Pubkey,
+    nfnode_type: &NfNodeType,
+    mint_authority_entry: Option<&MintAuthorityEntry>
+) -> Result<()> {
+    if
+        *collection != Pubkey::default() &&
+        verify_collection_member(nft_mint_info, collection, nfnode_type).is_ok()
+    {
+        return Ok(());
+    }
+    let nft_mint_data = nft_mint_info.try_borrow_data()?;
+    let nft_mint = StateWithExtensions::<Mint>::unpack(&nft_mint_data).map_err(
+        |_| error!(RewardError::InvalidNftMint)
+    )?;
+    let mint_authority = Option::<Pubkey>::from(nft_mint.base.mint_authority).ok_or(
+        RewardError::UnauthorizedMintAuthority
+    )?;
+    let mint_authority_entry = mint_authority_entry.ok_or(RewardError::UnauthorizedMintAuthority)?;
+    require!(
+        mint_authority_entry.authority == mint_authority,
+        RewardError::UnauthorizedMintAuthority
+    );
+    Ok(())
+}
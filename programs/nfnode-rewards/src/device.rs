@@ -0,0 +1,83 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        sysvar::instructions::{ load_current_index_checked, load_instruction_at_checked },
+    },
+};
+
+use crate::errors::RewardError;
+// domain separator of device signed heartbeats
+pub const HEARTBEAT_MESSAGE_PREFIX: &[u8] = b"wayru-nfnode-heartbeat";
+// heartbeats more than this many seconds away from the cluster clock are rejected
+pub const HEARTBEAT_MAX_CLOCK_DRIFT: i64 = 300;
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+const SIGNATURE_SIZE: usize = 64;
+
+// message signed by the device: prefix, nfnode asset key and heartbeat timestamp
+pub fn heartbeat_message(nft_mint: &Pubkey, timestamp: i64) -> Vec<u8> {
+    let mut message = HEARTBEAT_MESSAGE_PREFIX.to_vec();
+    message.extend_from_slice(nft_mint.as_ref());
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message
+}
+
+// the instruction right before the current one must be an Ed25519 precompile
+// verifying a single signature of the device over the message
+pub fn verify_device_signature(
+    instructions_sysvar: &AccountInfo,
+    device_pubkey: &Pubkey,
+    message: &[u8]
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, RewardError::InvalidDeviceSignature);
+    let ed25519_instruction = load_instruction_at_checked(
+        (current_index - 1) as usize,
+        instructions_sysvar
+    )?;
+    require_keys_eq!(
+        ed25519_instruction.program_id,
+        ed25519_program::ID,
+        RewardError::InvalidDeviceSignature
+    );
+    let data = &ed25519_instruction.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE && data[0] == 1,
+        RewardError::InvalidDeviceSignature
+    );
+    let read_u16 = |offset: usize| {
+        let start = SIGNATURE_OFFSETS_START + offset;
+        u16::from_le_bytes([data[start], data[start + 1]])
+    };
+    let signature_offset = read_u16(0) as usize;
+    let signature_instruction_index = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_instruction_index = read_u16(6);
+    let message_data_offset = read_u16(8) as usize;
+    let message_data_size = read_u16(10) as usize;
+    let message_instruction_index = read_u16(12);
+    // every part must live in the precompile instruction itself
+    require!(
+        signature_instruction_index == u16::MAX &&
+            public_key_instruction_index == u16::MAX &&
+            message_instruction_index == u16::MAX,
+        RewardError::InvalidDeviceSignature
+    );
+    require!(
+        data.len() >= signature_offset + SIGNATURE_SIZE &&
+            data.len() >= public_key_offset + PUBKEY_SIZE &&
+            data.len() >= message_data_offset + message_data_size,
+        RewardError::InvalidDeviceSignature
+    );
+    require!(
+        data[public_key_offset..public_key_offset + PUBKEY_SIZE] == device_pubkey.to_bytes(),
+        RewardError::InvalidDeviceSignature
+    );
+    require!(
+        data[message_data_offset..message_data_offset + message_data_size] == *message,
+        RewardError::InvalidDeviceSignature
+    );
+    Ok(())
+}
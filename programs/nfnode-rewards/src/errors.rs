@@ -43,7 +43,7 @@ pub enum RewardError {
     InvalidRewardAmount,
     #[msg("Funding amount must be greater than zero.")]
     InvalidFundingAmount,
-    #[msg("Deposit amount must be equal than 5000000000.")]
+    #[msg("Deposit amount must be greater than zero.")]
     InvalidDepositAmount,
     #[msg("Deposit already made.")]
     DepositAlreadyMade,
@@ -62,5 +62,115 @@ pub enum RewardError {
     #[msg("Mint authority not found.")]
     MintAuthorityNotFound,
     #[msg("Mint authority list is full.")]
-    MintAuthorityListFull,    
+    MintAuthorityListFull,
+    #[msg("Invalid remaining accounts.")]
+    InvalidRemainingAccounts,
+    #[msg("Invalid payout destination.")]
+    InvalidPayoutDestination,
+    #[msg("Crank fee must be lower than the reward amount.")]
+    InvalidCrankFee,
+    #[msg("Compound share must be at most 10000 basis points.")]
+    InvalidCompoundShare,
+    #[msg("Deposit storage account required.")]
+    DepositStorageRequired,
+    #[msg("Debt withholding must be between 1 and 10000 basis points.")]
+    InvalidDebtWithholding,
+    #[msg("Deposit debt still outstanding.")]
+    DepositDebtOutstanding,
+    #[msg("Account already migrated.")]
+    AlreadyMigrated,
+    #[msg("Invalid mint authority grant.")]
+    InvalidMintAuthorityGrant,
+    #[msg("NfNode registered outside the mint authority grant window.")]
+    MintAuthorityGrantExpired,
+    #[msg("Mint authority is not allowed to mint this NfNode type.")]
+    NfNodeTypeNotAllowed,
+    #[msg("Invalid collection.")]
+    InvalidCollection,
+    #[msg("NFT is not a member of the NfNode collection.")]
+    NotCollectionMember,
+    #[msg("Invalid NFT metadata.")]
+    InvalidNftMetadata,
+    #[msg("NfNode type does not match the NFT metadata.")]
+    NfNodeTypeMismatch,
+    #[msg("Invalid identity source.")]
+    InvalidIdentitySource,
+    #[msg("Invalid asset ownership proof.")]
+    InvalidAssetProof,
+    #[msg("Device already bound.")]
+    DeviceAlreadyBound,
+    #[msg("No device bound to the NfNode.")]
+    DeviceNotBound,
+    #[msg("Invalid device signature.")]
+    InvalidDeviceSignature,
+    #[msg("Invalid heartbeat timestamp.")]
+    InvalidHeartbeatTimestamp,
+    #[msg("Device has not sent a recent heartbeat.")]
+    DeviceOffline,
+    #[msg("Invalid H3 cell.")]
+    InvalidH3Cell,
+    #[msg("Invalid H3 region account.")]
+    InvalidH3Region,
+    #[msg("Location asserted too recently.")]
+    LocationAssertionTooFrequent,
+    #[msg("Region multiplier must be at most 30000 basis points.")]
+    InvalidRegionMultiplier,
+    #[msg("Invalid claim period.")]
+    InvalidClaimPeriod,
+    #[msg("Invalid emergency withdrawal grace period.")]
+    InvalidGracePeriod,
+    #[msg("Invalid pause flags or expiry.")]
+    InvalidPauseFlags,
+    #[msg("NfNode is frozen.")]
+    NfNodeFrozen,
+    #[msg("NfNode is not frozen.")]
+    NfNodeNotFrozen,
+    #[msg("Wallet is blacklisted.")]
+    WalletBlacklisted,
+    #[msg("Appeal already filed.")]
+    AppealAlreadyFiled,
+    #[msg("Protocol fee and burn must be at most 5000 basis points.")]
+    InvalidProtocolCut,
+    #[msg("Invalid treasury token account.")]
+    InvalidTreasuryTokenAccount,
+    #[msg("Stream rate must be greater than zero.")]
+    InvalidStreamRate,
+    #[msg("Nothing has accrued on the stream since the last drip.")]
+    NothingToDrip,
+    #[msg("Reserve ratio must be at most 9000 basis points.")]
+    InvalidReserveRatio,
+    #[msg("Nothing is owed on this reward entry.")]
+    NothingOwed,
+    #[msg("Reward pool balance is too low for this claim.")]
+    PoolBalanceTooLow,
+    #[msg("Stake vault account is required while the nfnode has delegated stake.")]
+    StakeVaultRequired,
+    #[msg("Stake commission must be at most 10000 basis points.")]
+    InvalidStakeCommission,
+    #[msg("Stake amount must be greater than zero.")]
+    InvalidStakeAmount,
+    #[msg("Not enough stake in this position.")]
+    InsufficientStake,
+    #[msg("Unstaked tokens are still cooling down.")]
+    UnstakeCooldownActive,
+    #[msg("No stake rewards to claim.")]
+    NoStakeRewards,
+    #[msg("Withdraw amount must be greater than zero and at most the deposit.")]
+    InvalidWithdrawAmount,
+    #[msg("A deposit's lockup tier can not be shortened.")]
+    LockupTierDowngrade,
+    #[msg("The required deposit can only be withdrawn by unbonding.")]
+    UnbondingRequired,
+    #[msg("NfNode deposit is unbonding.")]
+    NfNodeUnbonding,
+    #[msg("NfNode deposit is not unbonding.")]
+    NotUnbonding,
+    #[msg("Unbonding period is not over yet.")]
+    UnbondingPeriodActive,
+    #[msg("Only the depositor can withdraw a deposit that does not carry with the NFT.")]
+    NotDepositor,
+    #[msg("Stake would exceed the cap set by the nfnode owner.")]
+    StakeCapExceeded,
+    #[msg("Invalid blacklist entry.")]
+    InvalidBlacklistEntry,
 }
\ No newline at end of file
@@ -0,0 +1,166 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{ instruction::{ AccountMeta, Instruction }, keccak, program::invoke },
+};
+use solana_program::pubkey;
+
+use crate::{
+    errors::RewardError,
+    state::{ CompressedNftProof, IdentitySource, NfNodeEntry, NfNodeIdentity },
+};
+pub const MPL_CORE_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+pub const BUBBLEGUM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+pub const ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+// Key::AssetV1 of a Metaplex Core asset account
+const CORE_ASSET_V1_KEY: u8 = 1;
+// sha256("global:verify_leaf")[..8]
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+// leading fields of a Metaplex Core BaseAssetV1 account
+#[allow(dead_code)] // the Address variant is decoded but never inspected
+#[derive(AnchorDeserialize)]
+enum CoreUpdateAuthority {
+    None,
+    Address(Pubkey),
+    Collection(Pubkey),
+}
+#[derive(AnchorDeserialize)]
+struct CoreAssetHeader {
+    key: u8,
+    owner: Pubkey,
+    update_authority: CoreUpdateAuthority,
+}
+
+// checks the owner holds the asset and that it comes from the identity source
+pub fn verify_asset_owner<'info>(
+    identity_source: &IdentitySource,
+    asset_info: &AccountInfo<'info>,
+    merkle_tree: Option<&AccountInfo<'info>>,
+    compression_program: Option<&AccountInfo<'info>>,
+    proof_accounts: &[AccountInfo<'info>],
+    compressed_nft_proof: Option<&CompressedNftProof>,
+    owner: &Pubkey
+) -> Result<()> {
+    match identity_source.identity {
+        NfNodeIdentity::CoreAsset => verify_core_asset(asset_info, owner, &identity_source.source),
+        NfNodeIdentity::CompressedNft => {
+            let merkle_tree = merkle_tree.ok_or(RewardError::InvalidAssetProof)?;
+            let compression_program = compression_program.ok_or(RewardError::InvalidAssetProof)?;
+            let compressed_nft_proof = compressed_nft_proof.ok_or(RewardError::InvalidAssetProof)?;
+            require_keys_eq!(
+                merkle_tree.key(),
+                identity_source.source,
+                RewardError::InvalidIdentitySource
+            );
+            verify_compressed_nft(
+                asset_info,
+                merkle_tree,
+                compression_program,
+                proof_accounts,
+                compressed_nft_proof,
+                owner
+            )
+        }
+        NfNodeIdentity::Token2022 => err!(RewardError::InvalidIdentitySource),
+    }
+}
+
+fn verify_core_asset(asset_info: &AccountInfo, owner: &Pubkey, collection: &Pubkey) -> Result<()> {
+    require_keys_eq!(*asset_info.owner, MPL_CORE_ID, RewardError::InvalidAssetProof);
+    let asset_data = asset_info.try_borrow_data()?;
+    let asset = CoreAssetHeader::deserialize(&mut &asset_data[..]).map_err(
+        |_| error!(RewardError::InvalidAssetProof)
+    )?;
+    require!(asset.key == CORE_ASSET_V1_KEY, RewardError::InvalidAssetProof);
+    require_keys_eq!(asset.owner, *owner, RewardError::InsufficientNftBalance);
+    match asset.update_authority {
+        CoreUpdateAuthority::Collection(asset_collection) if asset_collection == *collection => Ok(()),
+        _ => err!(RewardError::NotCollectionMember),
+    }
+}
+
+// Core asset or compressed nft leaf and the accounts proving who holds it
+pub struct AssetHolderProof<'a, 'info> {
+    pub identity_source: Option<&'a Account<'info, IdentitySource>>,
+    pub asset: AccountInfo<'info>,
+    pub merkle_tree: Option<&'a AccountInfo<'info>>,
+    pub compression_program: Option<&'a AccountInfo<'info>>,
+    pub proof_accounts: &'a [AccountInfo<'info>],
+    pub compressed_nft_proof: Option<&'a CompressedNftProof>,
+}
+
+// whether the signer proved to hold the asset of a deposit, the depositor of a deposit that does
+// not carry with the asset skips the proof
+pub fn proves_asset_holder(
+    nfnode_entry: &NfNodeEntry,
+    user: &Pubkey,
+    proof: AssetHolderProof
+) -> Result<bool> {
+    if nfnode_entry.deposit_belongs_to(user, false) {
+        return Ok(false);
+    }
+    let identity_source = proof.identity_source.ok_or(RewardError::InvalidIdentitySource)?;
+    require!(
+        identity_source.identity == nfnode_entry.identity,
+        RewardError::InvalidIdentitySource
+    );
+    verify_asset_owner(
+        identity_source,
+        &proof.asset,
+        proof.merkle_tree,
+        proof.compression_program,
+        proof.proof_accounts,
+        proof.compressed_nft_proof,
+        user
+    )?;
+    Ok(true)
+}
+
+pub fn compressed_nft_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    let (asset_id, _bump_seed) = Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        &BUBBLEGUM_ID
+    );
+    asset_id
+}
+
+fn verify_compressed_nft<'info>(
+    asset_info: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    compression_program: &AccountInfo<'info>,
+    proof_accounts: &[AccountInfo<'info>],
+    compressed_nft_proof: &CompressedNftProof,
+    owner: &Pubkey
+) -> Result<()> {
+    require_keys_eq!(compression_program.key(), ACCOUNT_COMPRESSION_ID, RewardError::InvalidAssetProof);
+    let asset_id = compressed_nft_asset_id(&merkle_tree.key(), compressed_nft_proof.nonce);
+    require_keys_eq!(asset_id, asset_info.key(), RewardError::InvalidAssetProof);
+
+    // Bubblegum LeafSchema::V1 hash
+    let leaf = keccak::hashv(
+        &[
+            &[1u8],
+            asset_id.as_ref(),
+            owner.as_ref(),
+            compressed_nft_proof.delegate.as_ref(),
+            &compressed_nft_proof.nonce.to_le_bytes(),
+            &compressed_nft_proof.data_hash,
+            &compressed_nft_proof.creator_hash,
+        ]
+    );
+
+    let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&compressed_nft_proof.root);
+    data.extend_from_slice(&leaf.to_bytes());
+    data.extend_from_slice(&compressed_nft_proof.index.to_le_bytes());
+    let mut accounts = vec![AccountMeta::new_readonly(merkle_tree.key(), false)];
+    accounts.extend(
+        proof_accounts.iter().map(|proof_account| AccountMeta::new_readonly(proof_account.key(), false))
+    );
+    let mut account_infos = vec![merkle_tree.clone()];
+    account_infos.extend(proof_accounts.iter().cloned());
+    account_infos.push(compression_program.clone());
+    // fails unless the leaf is in the tree under the given root
+    invoke(&(Instruction { program_id: ACCOUNT_COMPRESSION_ID, accounts, data }), &account_infos)?;
+    Ok(())
+}
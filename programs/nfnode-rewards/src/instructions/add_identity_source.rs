@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ AdminAccount, IdentitySource, NfNodeIdentity } };
+pub fn add_identity_source(
+    ctx: Context<AddIdentitySource>,
+    source: Pubkey,
+    identity: NfNodeIdentity
+) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    require!(source != Pubkey::default(), RewardError::InvalidPubkey); // Non-zero address validation
+    // token-2022 nfts are authorized through mint authorities and the nfnode collection
+    require!(identity != NfNodeIdentity::Token2022, RewardError::InvalidIdentitySource);
+    let identity_source = &mut ctx.accounts.identity_source;
+    identity_source.version = IdentitySource::VERSION;
+    identity_source.identity = identity;
+    identity_source.source = source;
+    Ok(())
+}
+#[derive(Accounts)]
+#[instruction(source: Pubkey)]
+pub struct AddIdentitySource<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + IdentitySource::INIT_SPACE,
+        seeds = [b"identity_source", source.as_ref()],
+        bump
+    )]
+    pub identity_source: Account<'info, IdentitySource>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,26 +1,36 @@
 use anchor_lang::prelude::*;
 
-use crate::{ errors::RewardError, state::{ AdminAccount, MAX_MINT_AUTHORITIES } };
+use crate::{ errors::RewardError, state::{ AdminAccount, MintAuthorityEntry, NfNodeType } };
 pub fn add_mint_authority(
     ctx: Context<AddMintAuthority>,
-    new_mint_authority: Pubkey
+    new_mint_authority: Pubkey,
+    registration_opens_at: i64,
+    registration_closes_at: i64,
+    allowed_nfnode_types: Vec<NfNodeType>
 ) -> Result<()> {
-    let admin_account = &mut ctx.accounts.admin_account;
+    let admin_account = &ctx.accounts.admin_account;
     require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
     require!(new_mint_authority != Pubkey::default(), RewardError::InvalidPubkey); // Non-zero address validation
-    if admin_account.mint_authorities.len() >= MAX_MINT_AUTHORITIES {
-        return Err(RewardError::MintAuthorityListFull.into());
-    }
-    if admin_account.mint_authorities.contains(&new_mint_authority) {
-        return Err(RewardError::MintAuthorityAlreadyExists.into());
-    }
-    admin_account.mint_authorities.push(new_mint_authority);
+    let mint_authority_entry = &mut ctx.accounts.mint_authority_entry;
+    mint_authority_entry.authority = new_mint_authority;
+    mint_authority_entry.set_grant(registration_opens_at, registration_closes_at, &allowed_nfnode_types)?;
     Ok(())
 }
 #[derive(Accounts)]
+#[instruction(new_mint_authority: Pubkey)]
 pub struct AddMintAuthority<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(mut, seeds = [b"admin_account"], bump)]
+    #[account(seeds = [b"admin_account"], bump)]
     pub admin_account: Account<'info, AdminAccount>,
+    // init fails if the authority was already added
+    #[account(
+        init,
+        payer = user,
+        space = 8 + MintAuthorityEntry::INIT_SPACE,
+        seeds = [b"mint_authority", new_mint_authority.as_ref()],
+        bump
+    )]
+    pub mint_authority_entry: Account<'info, MintAuthorityEntry>,
+    pub system_program: Program<'info, System>,
 }
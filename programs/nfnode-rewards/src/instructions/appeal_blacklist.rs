@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::BlacklistEntry };
+// flags the entry for admin review, the admin answers with remove_blacklisted_wallet or by keeping it
+pub fn appeal_blacklist(ctx: Context<AppealBlacklist>) -> Result<()> {
+    let blacklist_entry = &mut ctx.accounts.blacklist_entry;
+    require!(!blacklist_entry.appealed, RewardError::AppealAlreadyFiled);
+    blacklist_entry.appealed = true;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct AppealBlacklist<'info> {
+    pub user: Signer<'info>,
+    #[account(mut, seeds = [b"blacklist", user.key().as_ref()], bump)]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+}
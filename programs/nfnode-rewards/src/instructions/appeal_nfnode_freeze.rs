@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount as SplToken2022Account;
+
+use crate::{ errors::RewardError, state::NfNodeEntry };
+// flags the freeze for admin review, the admin answers with unfreeze_nfnode or by keeping it frozen
+pub fn appeal_nfnode_freeze(ctx: Context<AppealNfNodeFreeze>) -> Result<()> {
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    let user = ctx.accounts.user.key();
+    // owners prove it with their token-2022 nft account, hosts and manufacturers by their key
+    let is_owner = ctx.accounts.user_nft_token_account
+        .as_ref()
+        .is_some_and(|user_nft_token_account| user_nft_token_account.amount > 0);
+    require!(
+        is_owner || user == nfnode_entry.host || user == nfnode_entry.manufacturer,
+        RewardError::UnauthorizedUser
+    );
+    require!(nfnode_entry.frozen, RewardError::NfNodeNotFrozen);
+    require!(!nfnode_entry.freeze_appealed, RewardError::AppealAlreadyFiled);
+    nfnode_entry.freeze_appealed = true;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct AppealNfNodeFreeze<'info> {
+    pub user: Signer<'info>,
+    /// CHECK: only used to derive the nfnode entry
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(token::mint = nft_mint_address, token::authority = user)]
+    pub user_nft_token_account: Option<InterfaceAccount<'info, SplToken2022Account>>,
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
+};
+use crate::{
+    errors::RewardError,
+    location::validate_h3_cell,
+    state::{
+        NfNodeEntry,
+        NfNodeLocation,
+        AdminAccount,
+        FunderEntry,
+        RewardPool,
+        H3_REGION_RESOLUTION,
+        LOCATION_ASSERTION_COOLDOWN,
+        LOCATION_ASSERTION_FEE,
+        PAUSE_REGISTRATION,
+    },
+};
+pub fn assert_location(ctx: Context<AssertLocation>, h3_cell: u64) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(
+        !admin_account.is_paused(PAUSE_REGISTRATION, Clock::get()?.unix_timestamp),
+        RewardError::ProgramPaused
+    );
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+    let nfnode_entry = &ctx.accounts.nfnode_entry;
+    let user = ctx.accounts.user.key();
+    require!(
+        user == nfnode_entry.host || user == nfnode_entry.manufacturer,
+        RewardError::UnauthorizedUser
+    );
+    // the cell must be at least as fine as the region cells multipliers are set for
+    validate_h3_cell(h3_cell, H3_REGION_RESOLUTION)?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let nfnode_location = &mut ctx.accounts.nfnode_location;
+    require!(
+        nfnode_location.assertion_timestamp == 0 ||
+            current_timestamp - nfnode_location.assertion_timestamp >= LOCATION_ASSERTION_COOLDOWN,
+        RewardError::LocationAssertionTooFrequent
+    );
+
+    // the assertion fee funds the reward pool
+    token::transfer(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.token_storage_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        }),
+        LOCATION_ASSERTION_FEE
+    )?;
+    ctx.accounts.reward_pool.record_funding(
+        &mut ctx.accounts.funder_entry,
+        user,
+        LOCATION_ASSERTION_FEE,
+        current_timestamp
+    )?;
+
+    nfnode_location.version = NfNodeLocation::VERSION;
+    nfnode_location.h3_cell = h3_cell;
+    nfnode_location.asserted_by = user;
+    nfnode_location.assertion_timestamp = current_timestamp;
+    nfnode_location.assertion_fee = LOCATION_ASSERTION_FEE;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct AssertLocation<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: only used to derive the nfnode accounts
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + NfNodeLocation::INIT_SPACE,
+        seeds = [b"nfnode_location", nft_mint_address.key().as_ref()],
+        bump
+    )]
+    pub nfnode_location: Box<Account<'info, NfNodeLocation>>,
+    pub token_mint: Box<Account<'info, Mint>>,
+    /// CHECK:
+    #[account(seeds = [b"token_storage"], bump)]
+    pub token_storage_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority,
+    )]
+    pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    // the fee is credited to the asserting wallet as a funder of the pool
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FunderEntry::INIT_SPACE,
+        seeds = [b"funder", user.key().as_ref()],
+        bump
+    )]
+    pub funder_entry: Box<Account<'info, FunderEntry>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Mint, Token, TokenAccount, Transfer },
+};
+use crate::{
+    errors::RewardError,
+    identity::{ proves_asset_holder, AssetHolderProof },
+    state::{ AdminAccount, NfNodeEntry, IdentitySource, CompressedNftProof, PAUSE_WITHDRAWALS },
+};
+// complete_withdrawal for Core and compressed nfnodes, the escrow is keyed by the asset id
+pub fn asset_complete_withdrawal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetCompleteWithdrawal<'info>>,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let nfnode_entry = &ctx.accounts.nfnode_entry;
+    // paid to the depositor, or to the asset holder when the deposit carries with the asset
+    let holds_asset = proves_asset_holder(
+        nfnode_entry,
+        &user,
+        AssetHolderProof {
+            identity_source: ctx.accounts.identity_source.as_deref(),
+            asset: ctx.accounts.asset.to_account_info(),
+            merkle_tree: ctx.accounts.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+            compression_program: ctx.accounts.compression_program
+                .as_ref()
+                .map(|program| program.as_ref()),
+            proof_accounts: ctx.remaining_accounts,
+            compressed_nft_proof: compressed_nft_proof.as_ref(),
+        }
+    )?;
+    nfnode_entry.require_deposit_owner(&user, holds_asset)?;
+    let admin_account = &ctx.accounts.admin_account;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let emergency_withdrawal_open = admin_account.emergency_withdrawal_open(current_timestamp);
+    // a pause past its grace period no longer locks deposits in escrow
+    require!(
+        !admin_account.is_paused(PAUSE_WITHDRAWALS, current_timestamp) || emergency_withdrawal_open,
+        RewardError::ProgramPaused
+    );
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+    nfnode_entry.require_unbonded(emergency_withdrawal_open, current_timestamp)?;
+
+    let amount = nfnode_entry.deposit_amount;
+    let authority_bump = ctx.bumps.token_storage_authority;
+    let binding = ctx.accounts.asset.key();
+    let authority_seeds = &[&b"token_storage"[..], binding.as_ref(), &[authority_bump]];
+    let signer_seeds = &[&authority_seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.token_storage_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.token_storage_authority.to_account_info(),
+            },
+            signer_seeds
+        ),
+        amount
+    )?;
+
+    ctx.accounts.nfnode_entry.release_deposit(current_timestamp);
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct AssetCompleteWithdrawal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_mint: Account<'info, Mint>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    // identity source of the asset, left out by the depositor of a deposit that does not carry
+    pub identity_source: Option<Box<Account<'info, IdentitySource>>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(mut, seeds = [b"nfnode_entry", asset.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+    /// CHECK: nfnode deposit escrow authority
+    #[account(seeds = [b"token_storage", asset.key().as_ref()], bump)]
+    pub token_storage_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority
+    )]
+    pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{ AssociatedToken },
+    token::{ Token, TokenAccount, Mint }, //Wayru Token
+};
+use crate::{
+    claim::{ require_claimable, settle_claim, Claim, ClaimAccounts },
+    errors::RewardError,
+    identity::verify_asset_owner,
+    location::apply_multiplier,
+    state::{
+        RewardEntry,
+        NfNodeEntry,
+        AdminAccount,
+        IdentitySource,
+        CompressedNftProof,
+        ClaimRole,
+        AutoCompound,
+        NfNodeLocation,
+        RewardPool,
+    },
+};
+// crank_claim_rewards for Core and compressed nfnodes, the owner role proves the beneficiary holds the asset
+pub fn asset_crank_claim_rewards<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetCrankClaimRewards<'info>>,
+    role: ClaimRole,
+    reward_amount: u64,
+    nonce: u64,
+    crank_fee: u64,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    // Validate that the reward amount is greater than zero
+    require!(reward_amount > 0, RewardError::InvalidRewardAmount);
+    // coverage multiplier of the region the nfnode is located in
+    let reward_amount = apply_multiplier(
+        reward_amount,
+        NfNodeLocation::reward_multiplier_bps(
+            &ctx.accounts.nfnode_location,
+            ctx.accounts.h3_region.as_ref().map(|h3_region| h3_region.as_ref())
+        )?
+    )?;
+    let amount = 5000000000;
+    // longer deposit lockups boost owner rewards
+    let reward_amount = match role {
+        ClaimRole::Owner =>
+            apply_multiplier(
+                reward_amount,
+                ctx.accounts.nfnode_entry.lockup_multiplier_bps(amount)
+            )?,
+        _ => reward_amount,
+    };
+    // The crank fee is authorized by the admin signature and comes out of the reward
+    require!(crank_fee < reward_amount, RewardError::InvalidCrankFee);
+    require!(
+        ctx.accounts.admin_account.valid_mint == ctx.accounts.token_mint.key(),
+        RewardError::InvalidMint
+    );
+
+    let beneficiary = ctx.accounts.beneficiary.key();
+    // only the owner's deposit is compounded
+    let auto_compound = match role {
+        ClaimRole::Owner =>
+            AutoCompound::load_for(&ctx.accounts.auto_compound.to_account_info(), &beneficiary)?,
+        _ => None,
+    };
+    let claim = Claim {
+        role,
+        beneficiary,
+        reward_amount,
+        crank_fee,
+        auto_compound,
+        deposit_requirement: amount,
+        now: Clock::get()?.unix_timestamp,
+    };
+    require_claimable(
+        &ctx.accounts.admin_account,
+        &ctx.accounts.user_admin.to_account_info(),
+        &ctx.accounts.reward_entry,
+        &ctx.accounts.nfnode_entry,
+        nonce,
+        &claim,
+        &ctx.accounts.blacklist_entry
+    )?;
+
+    match claim.role {
+        ClaimRole::Owner => {
+            // Validate the beneficiary currently holds the asset
+            let identity_source = ctx.accounts.identity_source
+                .as_ref()
+                .ok_or(RewardError::InvalidIdentitySource)?;
+            require!(
+                identity_source.identity == ctx.accounts.nfnode_entry.identity,
+                RewardError::InvalidIdentitySource
+            );
+            verify_asset_owner(
+                identity_source,
+                &ctx.accounts.asset.to_account_info(),
+                ctx.accounts.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+                ctx.accounts.compression_program.as_ref().map(|program| program.as_ref()),
+                ctx.remaining_accounts,
+                compressed_nft_proof.as_ref(),
+                &beneficiary
+            )?;
+        }
+        _ => {
+            require!(
+                ctx.accounts.nfnode_entry.role_beneficiary(&claim.role) == Some(beneficiary),
+                RewardError::UnauthorizedUser
+            );
+        }
+    }
+
+    let authority_bump = ctx.bumps.token_storage_authority;
+    let authority_seeds = &[&b"token_storage"[..], &[authority_bump]];
+    let signer_seeds = &[&authority_seeds[..]];
+    settle_claim(
+        &mut ctx.accounts.reward_pool,
+        &mut ctx.accounts.reward_entry,
+        &mut ctx.accounts.nfnode_entry,
+        nonce,
+        claim,
+        ClaimAccounts {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            token_storage_account: &ctx.accounts.token_storage_account,
+            token_storage_authority: ctx.accounts.token_storage_authority.to_account_info(),
+            treasury_token_account: ctx.accounts.treasury_token_account
+                .as_ref()
+                .map(|treasury_token_account| treasury_token_account.to_account_info()),
+            payout_config: ctx.accounts.payout_config.to_account_info(),
+            beneficiary_token_account: ctx.accounts.beneficiary_token_account.to_account_info(),
+            recipient_token_account: ctx.accounts.recipient_token_account.as_deref(),
+            recipient_blacklist_entry: ctx.accounts.recipient_blacklist_entry
+                .as_ref()
+                .map(|recipient_blacklist_entry| recipient_blacklist_entry.to_account_info()),
+            nfnode_stake: Some(ctx.accounts.nfnode_stake.to_account_info()),
+            stake_vault_account: ctx.accounts.stake_vault_account
+                .as_ref()
+                .map(|stake_vault_account| stake_vault_account.to_account_info()),
+            deposit_storage_account: ctx.accounts.deposit_storage_account
+                .as_ref()
+                .map(|deposit_storage_account| deposit_storage_account.to_account_info()),
+            cranker_token_account: ctx.accounts.cranker_token_account
+                .as_ref()
+                .map(|cranker_token_account| cranker_token_account.to_account_info()),
+        },
+        signer_seeds
+    )
+}
+#[derive(Accounts)]
+pub struct AssetCrankClaimRewards<'info> {
+    /// CHECK:
+    #[account(mut)]
+    pub user_admin: Signer<'info>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    /// CHECK: validated against the nfnode entry or the asset holder for the claimed role
+    pub beneficiary: UncheckedAccount<'info>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    // identity source of the asset, required for the owner role
+    pub identity_source: Option<Box<Account<'info, IdentitySource>>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + RewardEntry::INIT_SPACE,
+        seeds = [b"reward_entry", beneficiary.key().as_ref(), asset.key().as_ref()],
+        bump
+    )]
+    pub reward_entry: Box<Account<'info, RewardEntry>>,
+    #[account(mut, seeds = [b"nfnode_entry", asset.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(mut)]
+    pub token_mint: Box<Account<'info, Mint>>,
+    /// CHECK:
+    #[account(mut, seeds = [b"token_storage"], bump)]
+    pub token_storage_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority,
+    )]
+    pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(mut, token::mint = token_mint)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: delegated stake on the nfnode, may be uninitialized
+    #[account(mut, seeds = [b"nfnode_stake", asset.key().as_ref()], bump)]
+    pub nfnode_stake: UncheckedAccount<'info>,
+    /// CHECK: delegated stake vault authority
+    #[account(seeds = [b"stake_vault", asset.key().as_ref()], bump)]
+    pub stake_vault_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = stake_vault_authority,
+    )]
+    pub stake_vault_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = beneficiary
+    )]
+    pub beneficiary_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: payout routing set by asset_set_payout_destination, may be uninitialized
+    #[account(seeds = [b"payout_config", asset.key().as_ref()], bump)]
+    pub payout_config: UncheckedAccount<'info>,
+    #[account(mut, token::mint = token_mint)]
+    pub recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: blacklist entry of the wallet owning recipient_token_account, checked by the handler
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
+    /// CHECK: auto-compound setting set by asset_set_auto_compound, may be uninitialized
+    #[account(seeds = [b"auto_compound", asset.key().as_ref()], bump)]
+    pub auto_compound: UncheckedAccount<'info>,
+    /// CHECK: nfnode deposit escrow authority
+    #[account(seeds = [b"token_storage", asset.key().as_ref()], bump)]
+    pub deposit_storage_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = deposit_storage_authority,
+    )]
+    pub deposit_storage_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(mut, token::mint = token_mint, token::authority = cranker)]
+    pub cranker_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: blacklist entry of the beneficiary, only initialized for blacklisted wallets
+    #[account(seeds = [b"blacklist", beneficiary.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    /// CHECK: location asserted by assert_location, may be uninitialized
+    #[account(seeds = [b"nfnode_location", asset.key().as_ref()], bump)]
+    pub nfnode_location: UncheckedAccount<'info>,
+    /// CHECK: region multiplier of the nfnode location, required once a location is asserted
+    pub h3_region: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
+};
+use crate::{
+    errors::RewardError,
+    identity::verify_asset_owner,
+    state::{ NfNodeEntry, AdminAccount, IdentitySource, CompressedNftProof, LockupTier, PAUSE_DEPOSITS },
+};
+// deposit_tokens for Core and compressed nfnodes, the escrow is keyed by the asset id
+pub fn asset_deposit_tokens<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetDepositTokens<'info>>,
+    amount: u64,
+    lockup_tier: LockupTier,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    require!(amount > 0, RewardError::InvalidDepositAmount);
+    let nfnode_entry = &ctx.accounts.nfnode_entry;
+    nfnode_entry.require_deposit_open(&lockup_tier)?;
+    let admin_account = &ctx.accounts.admin_account;
+    require!(
+        !admin_account.is_paused(PAUSE_DEPOSITS, Clock::get()?.unix_timestamp),
+        RewardError::ProgramPaused
+    );
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+
+    //validate the user owns the asset
+    let identity_source = &ctx.accounts.identity_source;
+    require!(
+        identity_source.identity == nfnode_entry.identity,
+        RewardError::InvalidIdentitySource
+    );
+    verify_asset_owner(
+        identity_source,
+        &ctx.accounts.asset.to_account_info(),
+        ctx.accounts.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+        ctx.accounts.compression_program.as_ref().map(|program| program.as_ref()),
+        ctx.remaining_accounts,
+        compressed_nft_proof.as_ref(),
+        &ctx.accounts.user.key()
+    )?;
+    // a top-up adds to a deposit that has to stay with a single owner
+    if nfnode_entry.deposit_amount > 0 {
+        nfnode_entry.require_deposit_owner(&ctx.accounts.user.key(), true)?;
+    }
+
+    token::transfer(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.token_storage_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        }),
+        amount
+    )?;
+    let user = ctx.accounts.user.key();
+    ctx.accounts.nfnode_entry.record_deposit(
+        &user,
+        amount,
+        lockup_tier,
+        Clock::get()?.unix_timestamp
+    )?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct AssetDepositTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_mint: Account<'info, Mint>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    pub identity_source: Box<Account<'info, IdentitySource>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(mut, seeds = [b"nfnode_entry", asset.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+    /// CHECK:
+    #[account(seeds = [b"token_storage", asset.key().as_ref()], bump)]
+    pub token_storage_authority: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority
+    )]
+    pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::{
+    identity::{ proves_asset_holder, AssetHolderProof },
+    state::{ AdminAccount, NfNodeEntry, IdentitySource, CompressedNftProof },
+};
+// request_withdrawal for Core and compressed nfnodes
+pub fn asset_request_withdrawal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetRequestWithdrawal<'info>>,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    ctx.accounts.require_deposit_owner(ctx.remaining_accounts, compressed_nft_proof.as_ref())?;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let emergency_withdrawal_open = ctx.accounts.admin_account.emergency_withdrawal_open(
+        current_timestamp
+    );
+    ctx.accounts.nfnode_entry.request_unbonding(emergency_withdrawal_open, current_timestamp)
+}
+// cancel_withdrawal for Core and compressed nfnodes
+pub fn asset_cancel_withdrawal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetRequestWithdrawal<'info>>,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    ctx.accounts.require_deposit_owner(ctx.remaining_accounts, compressed_nft_proof.as_ref())?;
+    ctx.accounts.nfnode_entry.cancel_unbonding()
+}
+#[derive(Accounts)]
+pub struct AssetRequestWithdrawal<'info> {
+    pub user: Signer<'info>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    // identity source of the asset, left out by the depositor of a deposit that does not carry
+    pub identity_source: Option<Box<Account<'info, IdentitySource>>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(mut, seeds = [b"nfnode_entry", asset.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+}
+impl<'info> AssetRequestWithdrawal<'info> {
+    fn require_deposit_owner(
+        &self,
+        proof_accounts: &[AccountInfo<'info>],
+        compressed_nft_proof: Option<&CompressedNftProof>
+    ) -> Result<()> {
+        let user = self.user.key();
+        let holds_asset = proves_asset_holder(
+            &self.nfnode_entry,
+            &user,
+            AssetHolderProof {
+                identity_source: self.identity_source.as_deref(),
+                asset: self.asset.to_account_info(),
+                merkle_tree: self.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+                compression_program: self.compression_program
+                    .as_ref()
+                    .map(|program| program.as_ref()),
+                proof_accounts,
+                compressed_nft_proof,
+            }
+        )?;
+        self.nfnode_entry.require_deposit_owner(&user, holds_asset)
+    }
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::RewardError,
+    identity::verify_asset_owner,
+    state::{ AutoCompound, NfNodeEntry, IdentitySource, CompressedNftProof },
+};
+// set_auto_compound for Core and compressed nfnodes
+pub fn asset_set_auto_compound<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetSetAutoCompound<'info>>,
+    compound_bps: u16,
+    stake_beyond_requirement: bool,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    require!(compound_bps <= 10000, RewardError::InvalidCompoundShare);
+    //validate the user owns the asset
+    let identity_source = &ctx.accounts.identity_source;
+    require!(
+        identity_source.identity == ctx.accounts.nfnode_entry.identity,
+        RewardError::InvalidIdentitySource
+    );
+    verify_asset_owner(
+        identity_source,
+        &ctx.accounts.asset.to_account_info(),
+        ctx.accounts.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+        ctx.accounts.compression_program.as_ref().map(|program| program.as_ref()),
+        ctx.remaining_accounts,
+        compressed_nft_proof.as_ref(),
+        &ctx.accounts.user.key()
+    )?;
+
+    // compound_bps = 0 disables auto-compounding
+    let auto_compound = &mut ctx.accounts.auto_compound;
+    auto_compound.version = AutoCompound::VERSION;
+    auto_compound.owner = ctx.accounts.user.key();
+    auto_compound.compound_bps = compound_bps;
+    auto_compound.stake_beyond_requirement = stake_beyond_requirement;
+    // the coverage period is not restarted by changing the setting or the asset changing hands
+    if compound_bps > 0 && auto_compound.enabled_at == 0 {
+        auto_compound.enabled_at = Clock::get()?.unix_timestamp;
+    }
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct AssetSetAutoCompound<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    pub identity_source: Box<Account<'info, IdentitySource>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"nfnode_entry", asset.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + AutoCompound::INIT_SPACE,
+        seeds = [b"auto_compound", asset.key().as_ref()],
+        bump
+    )]
+    pub auto_compound: Account<'info, AutoCompound>,
+    pub system_program: Program<'info, System>,
+}
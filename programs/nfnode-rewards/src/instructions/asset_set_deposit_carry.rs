@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::{
+    identity::{ proves_asset_holder, AssetHolderProof },
+    state::{ NfNodeEntry, IdentitySource, CompressedNftProof },
+};
+// set_deposit_carry for Core and compressed nfnodes, the holder of a carrying deposit proves
+// the asset
+pub fn asset_set_deposit_carry<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetSetDepositCarry<'info>>,
+    carries_with_nft: bool,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let holds_asset = proves_asset_holder(
+        &ctx.accounts.nfnode_entry,
+        &user,
+        AssetHolderProof {
+            identity_source: ctx.accounts.identity_source.as_deref(),
+            asset: ctx.accounts.asset.to_account_info(),
+            merkle_tree: ctx.accounts.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+            compression_program: ctx.accounts.compression_program
+                .as_ref()
+                .map(|program| program.as_ref()),
+            proof_accounts: ctx.remaining_accounts,
+            compressed_nft_proof: compressed_nft_proof.as_ref(),
+        }
+    )?;
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    nfnode_entry.require_deposit_owner(&user, holds_asset)?;
+    nfnode_entry.set_deposit_carry(&user, carries_with_nft)
+}
+#[derive(Accounts)]
+pub struct AssetSetDepositCarry<'info> {
+    pub user: Signer<'info>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    // identity source of the asset, left out by the depositor of a deposit that does not carry
+    pub identity_source: Option<Box<Account<'info, IdentitySource>>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(mut, seeds = [b"nfnode_entry", asset.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::RewardError,
+    identity::verify_asset_owner,
+    state::{ ClaimRole, NfNodeEntry, PayoutConfig, IdentitySource, CompressedNftProof },
+};
+// set_payout_destination for Core and compressed nfnodes, only the owner role proves the asset
+pub fn asset_set_payout_destination<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetSetPayoutDestination<'info>>,
+    role: ClaimRole,
+    destination: Pubkey,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    let nfnode_entry = &ctx.accounts.nfnode_entry;
+    let user = ctx.accounts.user.key();
+    match role {
+        ClaimRole::Owner => {
+            let identity_source = ctx.accounts.identity_source
+                .as_ref()
+                .ok_or(RewardError::InvalidIdentitySource)?;
+            require!(
+                identity_source.identity == nfnode_entry.identity,
+                RewardError::InvalidIdentitySource
+            );
+            verify_asset_owner(
+                identity_source,
+                &ctx.accounts.asset.to_account_info(),
+                ctx.accounts.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+                ctx.accounts.compression_program.as_ref().map(|program| program.as_ref()),
+                ctx.remaining_accounts,
+                compressed_nft_proof.as_ref(),
+                &user
+            )?;
+        }
+        ClaimRole::Host => {
+            require!(user == nfnode_entry.host, RewardError::UnauthorizedUser);
+        }
+        ClaimRole::Manufacturer => {
+            require!(user == nfnode_entry.manufacturer, RewardError::UnauthorizedUser);
+        }
+    }
+
+    // Pubkey::default() clears the route and pays the signer again
+    let payout_config = &mut ctx.accounts.payout_config;
+    payout_config.version = PayoutConfig::VERSION;
+    let route = payout_config.route_mut(&role);
+    route.beneficiary = user;
+    route.destination = destination;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct AssetSetPayoutDestination<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    // identity source of the asset, required for the owner role
+    pub identity_source: Option<Box<Account<'info, IdentitySource>>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"nfnode_entry", asset.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PayoutConfig::INIT_SPACE,
+        seeds = [b"payout_config", asset.key().as_ref()],
+        bump
+    )]
+    pub payout_config: Box<Account<'info, PayoutConfig>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::RewardError,
+    identity::verify_asset_owner,
+    state::{ NfNodeEntry, NfNodeStake, IdentitySource, CompressedNftProof },
+};
+// set_stake_commission for Core and compressed nfnodes
+pub fn asset_set_stake_commission<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetSetStakeCommission<'info>>,
+    commission_bps: u16,
+    stake_cap: u64,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    require!(commission_bps <= 10000, RewardError::InvalidStakeCommission);
+    //validate the user owns the asset
+    let identity_source = &ctx.accounts.identity_source;
+    require!(
+        identity_source.identity == ctx.accounts.nfnode_entry.identity,
+        RewardError::InvalidIdentitySource
+    );
+    verify_asset_owner(
+        identity_source,
+        &ctx.accounts.asset.to_account_info(),
+        ctx.accounts.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+        ctx.accounts.compression_program.as_ref().map(|program| program.as_ref()),
+        ctx.remaining_accounts,
+        compressed_nft_proof.as_ref(),
+        &ctx.accounts.user.key()
+    )?;
+    ctx.accounts.nfnode_stake.configure(
+        ctx.accounts.asset.key(),
+        commission_bps,
+        stake_cap,
+        Clock::get()?.unix_timestamp
+    );
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct AssetSetStakeCommission<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    pub identity_source: Box<Account<'info, IdentitySource>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"nfnode_entry", asset.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + NfNodeStake::INIT_SPACE,
+        seeds = [b"nfnode_stake", asset.key().as_ref()],
+        bump
+    )]
+    pub nfnode_stake: Account<'info, NfNodeStake>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
+};
+use crate::{
+    errors::RewardError,
+    identity::{ proves_asset_holder, AssetHolderProof },
+    state::{ NfNodeEntry, AdminAccount, IdentitySource, CompressedNftProof, PAUSE_WITHDRAWALS },
+};
+// withdraw_tokens for Core and compressed nfnodes, the depositor withdraws without proving
+// ownership of the asset
+pub fn asset_withdraw_tokens<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssetWithdrawTokens<'info>>,
+    amount: u64,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    let nfnode_entry = &ctx.accounts.nfnode_entry;
+    require!(nfnode_entry.deposit_amount > 0, RewardError::WithdrawAlreadyMade);
+    require!(
+        amount > 0 && amount <= nfnode_entry.deposit_amount,
+        RewardError::InvalidWithdrawAmount
+    );
+    require!(!nfnode_entry.unbonding(), RewardError::NfNodeUnbonding);
+    let admin_account = &ctx.accounts.admin_account;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    // a pause past its grace period no longer locks deposits in escrow
+    require!(
+        !admin_account.is_paused(PAUSE_WITHDRAWALS, current_timestamp) ||
+            admin_account.emergency_withdrawal_open(current_timestamp),
+        RewardError::ProgramPaused
+    );
+    require!(
+        !nfnode_entry.frozen || admin_account.emergency_withdrawal_open(current_timestamp),
+        RewardError::NfNodeFrozen
+    );
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+
+    let user = ctx.accounts.user.key();
+    // anyone but the depositor of a deposit that does not carry has to hold the asset
+    let holds_asset = proves_asset_holder(
+        nfnode_entry,
+        &user,
+        AssetHolderProof {
+            identity_source: Some(&ctx.accounts.identity_source),
+            asset: ctx.accounts.asset.to_account_info(),
+            merkle_tree: ctx.accounts.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+            compression_program: ctx.accounts.compression_program
+                .as_ref()
+                .map(|program| program.as_ref()),
+            proof_accounts: ctx.remaining_accounts,
+            compressed_nft_proof: compressed_nft_proof.as_ref(),
+        }
+    )?;
+    nfnode_entry.require_deposit_owner(&user, holds_asset)?;
+    let deposit_requirement = 5000000000;
+    require!(
+        amount <= nfnode_entry.deposit_amount - nfnode_entry.bonded_deposit(deposit_requirement),
+        RewardError::UnbondingRequired
+    );
+    let authority_bump = ctx.bumps.token_storage_authority;
+    let binding = ctx.accounts.asset.key();
+    let authority_seeds = &[&b"token_storage"[..], binding.as_ref(), &[authority_bump]];
+    let signer_seeds = &[&authority_seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.token_storage_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.token_storage_authority.to_account_info(),
+            },
+            signer_seeds
+        ),
+        amount
+    )?;
+
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    nfnode_entry.deposit_amount -= amount;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct AssetWithdrawTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_mint: Account<'info, Mint>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    pub identity_source: Box<Account<'info, IdentitySource>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(mut, seeds = [b"nfnode_entry", asset.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+    /// CHECK:
+    #[account(seeds = [b"token_storage", asset.key().as_ref()], bump)]
+    pub token_storage_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority
+    )]
+    pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
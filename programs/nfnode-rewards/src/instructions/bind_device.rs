@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ AdminAccount, NfNodeEntry } };
+pub fn bind_device(ctx: Context<BindDevice>, device_pubkey: Pubkey) -> Result<()> {
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    require!(ctx.accounts.user.key() == nfnode_entry.manufacturer, RewardError::UnauthorizedUser);
+    require!(device_pubkey != Pubkey::default(), RewardError::InvalidPubkey); // Non-zero address validation
+    require!(nfnode_entry.device_pubkey == Pubkey::default(), RewardError::DeviceAlreadyBound);
+    nfnode_entry.device_pubkey = device_pubkey;
+    // the device has to prove it is online before the next claim
+    nfnode_entry.last_heartbeat_timestamp = 0;
+    Ok(())
+}
+// replaces a broken or lost device, Pubkey::default() unbinds it
+pub fn rebind_device(ctx: Context<RebindDevice>, device_pubkey: Pubkey) -> Result<()> {
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    let user = ctx.accounts.user.key();
+    require!(
+        user == nfnode_entry.manufacturer || user == ctx.accounts.admin_account.admin_pubkey,
+        RewardError::UnauthorizedUser
+    );
+    nfnode_entry.device_pubkey = device_pubkey;
+    // heartbeats of the previous device do not count for the new one
+    nfnode_entry.last_heartbeat_timestamp = 0;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct BindDevice<'info> {
+    pub user: Signer<'info>,
+    /// CHECK: only used to derive the nfnode entry
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+}
+#[derive(Accounts)]
+pub struct RebindDevice<'info> {
+    pub user: Signer<'info>,
+    /// CHECK: only used to derive the nfnode entry
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ AdminAccount, BlacklistEntry } };
+pub fn blacklist_wallet(ctx: Context<BlacklistWallet>, wallet: Pubkey, reason: u16) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    require!(wallet != Pubkey::default(), RewardError::InvalidPubkey); // Non-zero address validation
+    let blacklist_entry = &mut ctx.accounts.blacklist_entry;
+    blacklist_entry.version = BlacklistEntry::VERSION;
+    blacklist_entry.wallet = wallet;
+    blacklist_entry.reason = reason;
+    blacklist_entry.blacklisted_at = Clock::get()?.unix_timestamp;
+    blacklist_entry.appealed = false;
+    Ok(())
+}
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BlacklistWallet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + BlacklistEntry::INIT_SPACE,
+        seeds = [b"blacklist", wallet.as_ref()],
+        bump
+    )]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ Mint, Token, TokenAccount },
+};
+
+use crate::{
+    claim::{ pay_claim, Claim, ClaimAccounts },
+    errors::RewardError,
+    state::{
+        AdminAccount,
+        AutoCompound,
+        BlacklistEntry,
+        ClaimRole,
+        NfNodeEntry,
+        RewardEntry,
+        RewardPool,
+        PAUSE_CLAIMS,
+    },
+};
+// pays out rewards a short pool left owed to one role, the reserve is there for this so the whole
+// balance is usable. the payment goes through the same payout routing, delegator share, debt
+// repayment and auto-compounding as the role's claims
+pub fn claim_owed(ctx: Context<ClaimOwed>, role: ClaimRole) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(!ctx.accounts.admin_account.is_paused(PAUSE_CLAIMS, now), RewardError::ProgramPaused);
+    require!(!ctx.accounts.nfnode_entry.frozen, RewardError::NfNodeFrozen);
+    BlacklistEntry::require_not_blacklisted(&ctx.accounts.blacklist_entry)?;
+    let owed_amount = *ctx.accounts.reward_entry.owed_amount_mut(&role);
+    require!(owed_amount > 0, RewardError::NothingOwed);
+    let paid_amount = owed_amount.min(ctx.accounts.token_storage_account.amount);
+    require!(paid_amount > 0, RewardError::PoolBalanceTooLow);
+
+    *ctx.accounts.reward_entry.owed_amount_mut(&role) = owed_amount - paid_amount;
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.outstanding_liabilities = reward_pool.outstanding_liabilities.saturating_sub(
+        paid_amount
+    );
+
+    let auto_compound = match role {
+        ClaimRole::Owner =>
+            AutoCompound::load_for(
+                &ctx.accounts.auto_compound.to_account_info(),
+                &ctx.accounts.user.key()
+            )?,
+        _ => None,
+    };
+    let claim = Claim {
+        role,
+        beneficiary: ctx.accounts.user.key(),
+        reward_amount: paid_amount,
+        crank_fee: 0,
+        auto_compound,
+        deposit_requirement: 5000000000,
+        now,
+    };
+    let authority_bump = ctx.bumps.token_storage_authority;
+    let authority_seeds = &[&b"token_storage"[..], &[authority_bump]];
+    let signer_seeds = &[&authority_seeds[..]];
+    pay_claim(
+        &mut ctx.accounts.reward_pool,
+        &mut ctx.accounts.nfnode_entry,
+        claim,
+        ClaimAccounts {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            token_storage_account: &ctx.accounts.token_storage_account,
+            token_storage_authority: ctx.accounts.token_storage_authority.to_account_info(),
+            treasury_token_account: ctx.accounts.treasury_token_account
+                .as_ref()
+                .map(|treasury_token_account| treasury_token_account.to_account_info()),
+            payout_config: ctx.accounts.payout_config.to_account_info(),
+            beneficiary_token_account: ctx.accounts.user_token_account.to_account_info(),
+            recipient_token_account: ctx.accounts.recipient_token_account.as_deref(),
+            recipient_blacklist_entry: ctx.accounts.recipient_blacklist_entry
+                .as_ref()
+                .map(|recipient_blacklist_entry| recipient_blacklist_entry.to_account_info()),
+            nfnode_stake: Some(ctx.accounts.nfnode_stake.to_account_info()),
+            stake_vault_account: ctx.accounts.stake_vault_account
+                .as_ref()
+                .map(|stake_vault_account| stake_vault_account.to_account_info()),
+            deposit_storage_account: ctx.accounts.deposit_storage_account
+                .as_ref()
+                .map(|deposit_storage_account| deposit_storage_account.to_account_info()),
+            cranker_token_account: None,
+        },
+        signer_seeds
+    )
+}
+#[derive(Accounts)]
+pub struct ClaimOwed<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: token-2022 nft mint or asset id of the nfnode, only used to derive its accounts
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"reward_entry", user.key().as_ref(), nft_mint_address.key().as_ref()],
+        bump
+    )]
+    pub reward_entry: Account<'info, RewardEntry>,
+    #[account(
+        mut,
+        seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()],
+        bump
+    )]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+    /// CHECK:
+    #[account(mut, seeds = [b"token_storage"], bump)]
+    pub token_storage_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority,
+    )]
+    pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(mut, token::mint = token_mint)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: delegated stake on the nfnode, may be uninitialized
+    #[account(mut, seeds = [b"nfnode_stake", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_stake: UncheckedAccount<'info>,
+    /// CHECK: delegated stake vault authority
+    #[account(seeds = [b"stake_vault", nft_mint_address.key().as_ref()], bump)]
+    pub stake_vault_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = stake_vault_authority,
+    )]
+    pub stake_vault_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: payout routing set by set_payout_destination, may be uninitialized
+    #[account(seeds = [b"payout_config", nft_mint_address.key().as_ref()], bump)]
+    pub payout_config: UncheckedAccount<'info>,
+    #[account(mut, token::mint = token_mint)]
+    pub recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: blacklist entry of the wallet owning recipient_token_account, checked by the handler
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
+    /// CHECK: auto-compound setting set by set_auto_compound, may be uninitialized
+    #[account(seeds = [b"auto_compound", nft_mint_address.key().as_ref()], bump)]
+    pub auto_compound: UncheckedAccount<'info>,
+    /// CHECK: nfnode deposit escrow authority
+    #[account(seeds = [b"token_storage", nft_mint_address.key().as_ref()], bump)]
+    pub deposit_storage_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = deposit_storage_authority,
+    )]
+    pub deposit_storage_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: blacklist entry of the claimer, only initialized for blacklisted wallets
+    #[account(seeds = [b"blacklist", user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Mint, Token, TokenAccount, Transfer },
+};
+
+use crate::{
+    errors::RewardError,
+    state::{
+        AdminAccount,
+        BlacklistEntry,
+        NfNodeEntry,
+        NfNodeStake,
+        StakePosition,
+        PAUSE_CLAIMS,
+    },
+};
+// pays a delegator the rewards distributed to its stake, held in the stake vault, and activates
+// stake that has warmed up
+pub fn claim_stake_rewards(ctx: Context<ClaimStakeRewards>) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.admin_account.is_paused(PAUSE_CLAIMS, current_timestamp),
+        RewardError::ProgramPaused
+    );
+    require!(!ctx.accounts.nfnode_entry.frozen, RewardError::NfNodeFrozen);
+    BlacklistEntry::require_not_blacklisted(&ctx.accounts.blacklist_entry)?;
+    let nfnode_stake = &mut ctx.accounts.nfnode_stake;
+    let stake_position = &mut ctx.accounts.stake_position;
+    stake_position.settle(nfnode_stake)?;
+    let activated = stake_position.activate(nfnode_stake, current_timestamp)?;
+    let amount = stake_position.pending_rewards;
+    require!(amount > 0 || activated, RewardError::NoStakeRewards);
+    if amount == 0 {
+        return Ok(());
+    }
+    stake_position.pending_rewards = 0;
+
+    let nft_mint_address = ctx.accounts.nft_mint_address.key();
+    let vault_bump = ctx.bumps.stake_vault_authority;
+    let vault_seeds = &[&b"stake_vault"[..], nft_mint_address.as_ref(), &[vault_bump]];
+    let signer_seeds = &[&vault_seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault_account.to_account_info(),
+                to: ctx.accounts.delegator_token_account.to_account_info(),
+                authority: ctx.accounts.stake_vault_authority.to_account_info(),
+            },
+            signer_seeds
+        ),
+        amount
+    )?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct ClaimStakeRewards<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    /// CHECK: only used to derive the nfnode and stake accounts
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(mut, seeds = [b"nfnode_stake", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_stake: Box<Account<'info, NfNodeStake>>,
+    #[account(
+        mut,
+        seeds = [b"stake_position", nft_mint_address.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+    pub token_mint: Box<Account<'info, Mint>>,
+    /// CHECK: delegated stake vault authority
+    #[account(seeds = [b"stake_vault", nft_mint_address.key().as_ref()], bump)]
+    pub stake_vault_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = stake_vault_authority,
+    )]
+    pub stake_vault_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        associated_token::mint = token_mint,
+        associated_token::authority = delegator
+    )]
+    pub delegator_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: blacklist entry of the delegator, only initialized for blacklisted wallets
+    #[account(seeds = [b"blacklist", delegator.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, CloseAccount, Mint, Token, TokenAccount, Transfer },
+};
+
+use crate::{ errors::RewardError, state::{ AdminAccount, RewardStream } };
+// returns whatever the stream holds to the treasury, anything accrued but not dripped is returned too
+pub fn close_reward_stream(ctx: Context<CloseRewardStream>) -> Result<()> {
+    let treasury = ctx.accounts.treasury.key();
+    let stream_bump = ctx.bumps.reward_stream;
+    let stream_seeds = &[&b"reward_stream"[..], treasury.as_ref(), &[stream_bump]];
+    let signer_seeds = &[&stream_seeds[..]];
+    let amount = ctx.accounts.stream_token_account.amount;
+    if amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.stream_token_account.to_account_info(),
+                    to: ctx.accounts.treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.reward_stream.to_account_info(),
+                },
+                signer_seeds
+            ),
+            amount
+        )?;
+    }
+    token::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.stream_token_account.to_account_info(),
+                destination: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.reward_stream.to_account_info(),
+            },
+            signer_seeds
+        )
+    )?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct CloseRewardStream<'info> {
+    #[account(mut)]
+    pub treasury: Signer<'info>,
+    #[account(
+        mut,
+        close = treasury,
+        seeds = [b"reward_stream", treasury.key().as_ref()],
+        bump
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+    #[account(constraint = token_mint.key() == admin_account.valid_mint @ RewardError::InvalidMint)]
+    pub token_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = reward_stream,
+    )]
+    pub stream_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = treasury,
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Mint, Token, TokenAccount, Transfer },
+    token_interface::TokenAccount as SplToken2022Account,
+};
+
+use crate::{
+    errors::RewardError,
+    state::{ AdminAccount, NfNodeEntry, PAUSE_WITHDRAWALS },
+};
+// releases the whole deposit once the unbonding period of request_withdrawal is over. Once the
+// emergency withdrawal grace period has passed the deposit is released right away, frozen or not
+pub fn complete_withdrawal(ctx: Context<CompleteWithdrawal>) -> Result<()> {
+    let holds_nft = ctx.accounts.user_nft_token_account
+        .as_ref()
+        .is_some_and(|user_nft_token_account| user_nft_token_account.amount > 0);
+    let nfnode_entry = &ctx.accounts.nfnode_entry;
+    // paid to the depositor, or to the nft holder when the deposit carries with the nft
+    nfnode_entry.require_deposit_owner(&ctx.accounts.user.key(), holds_nft)?;
+    let admin_account = &ctx.accounts.admin_account;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let emergency_withdrawal_open = admin_account.emergency_withdrawal_open(current_timestamp);
+    // a pause past its grace period no longer locks deposits in escrow
+    require!(
+        !admin_account.is_paused(PAUSE_WITHDRAWALS, current_timestamp) || emergency_withdrawal_open,
+        RewardError::ProgramPaused
+    );
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+    nfnode_entry.require_unbonded(emergency_withdrawal_open, current_timestamp)?;
+
+    let amount = nfnode_entry.deposit_amount;
+    let authority_bump = ctx.bumps.token_storage_authority;
+    let binding = ctx.accounts.nft_mint_address.key();
+    let authority_seeds = &[&b"token_storage"[..], binding.as_ref(), &[authority_bump]];
+    let signer_seeds = &[&authority_seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.token_storage_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.token_storage_authority.to_account_info(),
+            },
+            signer_seeds
+        ),
+        amount
+    )?;
+
+    ctx.accounts.nfnode_entry.release_deposit(current_timestamp);
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct CompleteWithdrawal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_mint: Account<'info, Mint>,
+    /// CHECK: token-2022 nft mint of the nfnode
+    pub nft_mint_address: UncheckedAccount<'info>,
+    // left out by a depositor who no longer holds the nft
+    #[account(token::mint = nft_mint_address, token::authority = user)]
+    pub user_nft_token_account: Option<InterfaceAccount<'info, SplToken2022Account>>,
+    #[account(
+        mut,
+        seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()],
+        bump
+    )]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    /// CHECK: nfnode deposit escrow authority
+    #[account(seeds = [b"token_storage", nft_mint_address.key().as_ref()], bump)]
+    pub token_storage_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority
+    )]
+    pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
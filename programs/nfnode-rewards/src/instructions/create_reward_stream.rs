@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Mint, Token, TokenAccount, Transfer },
+};
+
+use crate::{
+    errors::RewardError,
+    state::{ AdminAccount, FunderEntry, RewardStream, PAUSE_FUNDING },
+};
+pub fn create_reward_stream(
+    ctx: Context<CreateRewardStream>,
+    amount: u64,
+    rate_per_second: u64
+) -> Result<()> {
+    require!(amount > 0, RewardError::InvalidFundingAmount);
+    require!(rate_per_second > 0, RewardError::InvalidStreamRate);
+    let admin_account = &ctx.accounts.admin_account;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    require!(!admin_account.is_paused(PAUSE_FUNDING, current_timestamp), RewardError::ProgramPaused);
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+
+    token::transfer(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), Transfer {
+            from: ctx.accounts.treasury_token_account.to_account_info(),
+            to: ctx.accounts.stream_token_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        }),
+        amount
+    )?;
+
+    let reward_stream = &mut ctx.accounts.reward_stream;
+    reward_stream.version = RewardStream::VERSION;
+    reward_stream.treasury = ctx.accounts.treasury.key();
+    reward_stream.total_amount = amount;
+    reward_stream.released_amount = 0;
+    reward_stream.rate_per_second = rate_per_second;
+    reward_stream.start_timestamp = current_timestamp;
+    reward_stream.last_drip_timestamp = current_timestamp;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct CreateRewardStream<'info> {
+    #[account(mut)]
+    pub treasury: Signer<'info>,
+    #[account(
+        init,
+        payer = treasury,
+        space = 8 + RewardStream::INIT_SPACE,
+        seeds = [b"reward_stream", treasury.key().as_ref()],
+        bump
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+    pub token_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    // the address is predictable, anyone can create it before the stream
+    #[account(
+        init_if_needed,
+        payer = treasury,
+        associated_token::mint = token_mint,
+        associated_token::authority = reward_stream
+    )]
+    pub stream_token_account: Account<'info, TokenAccount>,
+    // drips are credited to the treasury as a funder of the pool
+    #[account(
+        init_if_needed,
+        payer = treasury,
+        space = 8 + FunderEntry::INIT_SPACE,
+        seeds = [b"funder", treasury.key().as_ref()],
+        bump
+    )]
+    pub funder_entry: Account<'info, FunderEntry>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -4,13 +4,22 @@ use anchor_spl::{
     token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
     token_interface::{ Mint as Mint2022, TokenAccount as SplToken2022Account, TokenInterface },
 };
-use crate::{ errors::RewardError, state::{ NfNodeEntry, AdminAccount } };
-pub fn deposit_tokens(ctx: Context<DepositTokens>) -> Result<()> {
+use crate::{ collection::verify_nfnode_mint, errors::RewardError, state::{ NfNodeEntry, AdminAccount, LockupTier, MintAuthorityEntry, PAUSE_DEPOSITS } };
+// deposits can be made in several steps of any size, the lock applies to the required minimum
+pub fn deposit_tokens(
+    ctx: Context<DepositTokens>,
+    amount: u64,
+    lockup_tier: LockupTier
+) -> Result<()> {
+    require!(amount > 0, RewardError::InvalidDepositAmount);
     let nfnode_entry = &ctx.accounts.nfnode_entry;
-    require!(nfnode_entry.deposit_amount == 0, RewardError::DepositAlreadyMade);
+    nfnode_entry.require_deposit_open(&lockup_tier)?;
     // Validate that token_mint is a valid mint registered in admin account
     let admin_account = &ctx.accounts.admin_account;
-    require!(!admin_account.paused, RewardError::ProgramPaused);
+    require!(
+        !admin_account.is_paused(PAUSE_DEPOSITS, Clock::get()?.unix_timestamp),
+        RewardError::ProgramPaused
+    );
     let valid_mint = admin_account.valid_mint;
     let token_mint = &ctx.accounts.token_mint;
     require!(valid_mint == token_mint.key(), RewardError::InvalidMint);
@@ -55,21 +64,26 @@ pub fn deposit_tokens(ctx: Context<DepositTokens>) -> Result<()> {
     if user_nft_token_account.mint != ctx.accounts.nft_mint_address.key() {
         return err!(RewardError::InvalidNftMint);
     }
-    //validate if nft has valid mint authority
-    let metadata_account_info = &ctx.accounts.nft_mint_address.to_account_info();
-    let metadata_account_data = metadata_account_info.try_borrow_data()?;
-    let mint = Mint2022::try_deserialize(&mut &metadata_account_data[..])?;
-    let mint_authority = mint.mint_authority.unwrap();
-    require!(
-        admin_account.mint_authorities.contains(&mint_authority),
-        RewardError::UnauthorizedMintAuthority
-    );
+    // a top-up adds to a deposit that has to stay with a single owner
+    if nfnode_entry.deposit_amount > 0 {
+        nfnode_entry.require_deposit_owner(&ctx.accounts.user.key(), true)?;
+    }
+    //validate if nft has valid mint authority or is a member of the nfnode collection
+    verify_nfnode_mint(
+        &ctx.accounts.nft_mint_address.to_account_info(),
+        &admin_account.collection,
+        &nfnode_entry.nfnode_type,
+        ctx.accounts.mint_authority_entry.as_deref()
+    )?;
 
-    let amount = 5000000000;
     token::transfer(ctx.accounts.transfer_to_token_storage(), amount)?;
-    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
-    nfnode_entry.deposit_amount = amount;
-    nfnode_entry.deposit_timestamp = Clock::get()?.unix_timestamp;
+    let user = ctx.accounts.user.key();
+    ctx.accounts.nfnode_entry.record_deposit(
+        &user,
+        amount,
+        lockup_tier,
+        Clock::get()?.unix_timestamp
+    )?;
     Ok(())
 }
 #[derive(Accounts)]
@@ -89,6 +103,8 @@ pub struct DepositTokens<'info> {
     pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
     #[account(seeds = [b"admin_account"], bump)]
     pub admin_account: Account<'info, AdminAccount>,
+    // authorization record of the nft mint authority, not needed for members of the nfnode collection
+    pub mint_authority_entry: Option<Account<'info, MintAuthorityEntry>>,
     /// CHECK:
     #[account(mut, seeds = [b"token_storage",nft_mint_address.key().as_ref()], bump)]
     pub token_storage_authority: AccountInfo<'info>,
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Mint, Token, TokenAccount, Transfer },
+};
+
+use crate::{
+    errors::RewardError,
+    state::{ AdminAccount, FunderEntry, RewardPool, RewardStream, PAUSE_FUNDING },
+};
+// permissionless, moves whatever accrued on the stream into token_storage
+pub fn drip(ctx: Context<Drip>) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.admin_account.is_paused(PAUSE_FUNDING, current_timestamp),
+        RewardError::ProgramPaused
+    );
+    let amount = ctx.accounts.reward_stream.releasable_amount(current_timestamp)?;
+    require!(amount > 0, RewardError::NothingToDrip);
+
+    let treasury = ctx.accounts.reward_stream.treasury;
+    let stream_bump = ctx.bumps.reward_stream;
+    let stream_seeds = &[&b"reward_stream"[..], treasury.as_ref(), &[stream_bump]];
+    let signer_seeds = &[&stream_seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stream_token_account.to_account_info(),
+                to: ctx.accounts.token_storage_account.to_account_info(),
+                authority: ctx.accounts.reward_stream.to_account_info(),
+            },
+            signer_seeds
+        ),
+        amount
+    )?;
+
+    let reward_stream = &mut ctx.accounts.reward_stream;
+    reward_stream.released_amount = reward_stream.released_amount
+        .checked_add(amount)
+        .ok_or(RewardError::ArithmeticOverflow)?;
+    reward_stream.last_drip_timestamp = current_timestamp;
+    ctx.accounts.reward_pool.record_funding(
+        &mut ctx.accounts.funder_entry,
+        treasury,
+        amount,
+        current_timestamp
+    )
+}
+#[derive(Accounts)]
+pub struct Drip<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"reward_stream", reward_stream.treasury.as_ref()],
+        bump
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+    // only the reward mint is dripped, a stream account of any other mint is not the stream's budget
+    #[account(constraint = token_mint.key() == admin_account.valid_mint @ RewardError::InvalidMint)]
+    pub token_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = reward_stream,
+    )]
+    pub stream_token_account: Account<'info, TokenAccount>,
+    /// CHECK:
+    #[account(seeds = [b"token_storage"], bump)]
+    pub token_storage_authority: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority
+    )]
+    pub token_storage_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(mut, seeds = [b"funder", reward_stream.treasury.as_ref()], bump)]
+    pub funder_entry: Account<'info, FunderEntry>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ AdminAccount, NfNodeEntry } };
+pub fn freeze_nfnode(ctx: Context<FreezeNfNode>, reason: u16) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    nfnode_entry.frozen = true;
+    nfnode_entry.freeze_reason = reason;
+    nfnode_entry.freeze_appealed = false;
+    Ok(())
+}
+pub fn unfreeze_nfnode(ctx: Context<FreezeNfNode>) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    require!(nfnode_entry.frozen, RewardError::NfNodeNotFrozen);
+    nfnode_entry.frozen = false;
+    nfnode_entry.freeze_reason = 0;
+    nfnode_entry.freeze_appealed = false;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct FreezeNfNode<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: only used to derive the nfnode entry
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+}
@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{ AssociatedToken },
+    token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
+    token_interface::TokenInterface,
+};
+use crate::{ collection::verify_nfnode_mint, errors::RewardError, pool::{ collect_protocol_cut, ProtocolCutAccounts }, location::apply_multiplier, state::{
+    Host,
+    NfNodeEntry,
+    NfNodeIdentity,
+    AdminAccount,
+    MintAuthorityEntry,
+    ClaimRole,
+    PayoutConfig,
+    NfNodeLocation,
+    PAUSE_CLAIMS,
+    BlacklistEntry,
+    RewardPool,
+} };
+// next account of the nfnode groups passed in remaining accounts
+fn next_nfnode_account<'a, 'info>(
+    remaining_accounts: &mut std::slice::Iter<'a, AccountInfo<'info>>
+) -> Result<&'a AccountInfo<'info>> {
+    remaining_accounts.next().ok_or(error!(RewardError::InvalidRemainingAccounts))
+}
+pub fn host_claim_all<'info>(
+    ctx: Context<'_, '_, 'info, 'info, HostClaimAll<'info>>,
+    reward_amounts: Vec<u64>,
+    nonce: u64
+) -> Result<()> {
+    // Every nfnode is passed as a (nft_mint_address, nfnode_entry, payout_config, nfnode_location)
+    // group in remaining accounts, followed by its h3_region once a location is asserted
+    require!(!reward_amounts.is_empty(), RewardError::InvalidRewardAmount);
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+
+    let admin_account = &ctx.accounts.admin_account;
+    require!(
+        !admin_account.is_paused(PAUSE_CLAIMS, Clock::get()?.unix_timestamp),
+        RewardError::ProgramPaused
+    );
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+    let host_account = &mut ctx.accounts.host_account;
+    require!(
+        nonce > host_account.last_claimed_nonce ||
+            (host_account.last_claimed_nonce == 0 && nonce == 1) || // initialization
+            (host_account.last_claimed_nonce == u64::MAX && nonce == 1), // overflow unprobably
+        RewardError::NonceAlreadyClaimed
+    );
+    require!(
+        ctx.accounts.user_admin.key() == admin_account.admin_pubkey,
+        RewardError::UnauthorizedAdmin
+    );
+    let user_admin_account_info = ctx.accounts.user_admin.to_account_info();
+    let is_partially_signed_by_admin = user_admin_account_info.is_signer;
+    require!(is_partially_signed_by_admin, RewardError::MissingAdminSignature);
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    BlacklistEntry::require_not_blacklisted(&ctx.accounts.blacklist_entry)?;
+
+    // host rewards earlier batches left owed are paid first, from the whole balance like claim_owed
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    let owed_paid_amount = host_account.owed_amount.min(ctx.accounts.token_storage_account.amount);
+    host_account.owed_amount -= owed_paid_amount;
+    reward_pool.outstanding_liabilities = reward_pool.outstanding_liabilities.saturating_sub(
+        owed_paid_amount
+    );
+
+    let amount = 5000000000;
+    let mut total_reward_amount: u64 = owed_paid_amount;
+    let mut payout_destination: Option<Pubkey> = None;
+    for (index, reward_amount) in reward_amounts.iter().enumerate() {
+        require!(*reward_amount > 0, RewardError::InvalidRewardAmount);
+        let nft_mint_account_info = next_nfnode_account(remaining_accounts)?;
+        let nfnode_entry_info = next_nfnode_account(remaining_accounts)?;
+        let payout_config_info = next_nfnode_account(remaining_accounts)?;
+        let nfnode_location_info = next_nfnode_account(remaining_accounts)?;
+        let h3_region_info = if nfnode_location_info.data_is_empty() {
+            None
+        } else {
+            Some(next_nfnode_account(remaining_accounts)?)
+        };
+
+        let (derived_nfnode_entry, _bump_seed) = Pubkey::find_program_address(
+            &[b"nfnode_entry", nft_mint_account_info.key.as_ref()],
+            ctx.program_id
+        );
+        require!(derived_nfnode_entry == *nfnode_entry_info.key, RewardError::InvalidNfNodeEntry);
+        require!(nfnode_entry_info.is_writable, RewardError::InvalidRemainingAccounts);
+        let (derived_payout_config, _bump_seed) = Pubkey::find_program_address(
+            &[b"payout_config", nft_mint_account_info.key.as_ref()],
+            ctx.program_id
+        );
+        require!(
+            derived_payout_config == *payout_config_info.key,
+            RewardError::InvalidRemainingAccounts
+        );
+        let (derived_nfnode_location, _bump_seed) = Pubkey::find_program_address(
+            &[b"nfnode_location", nft_mint_account_info.key.as_ref()],
+            ctx.program_id
+        );
+        require!(
+            derived_nfnode_location == *nfnode_location_info.key,
+            RewardError::InvalidRemainingAccounts
+        );
+        // coverage multiplier of the region the nfnode is located in
+        let reward_amount = apply_multiplier(
+            *reward_amount,
+            NfNodeLocation::reward_multiplier_bps(nfnode_location_info, h3_region_info)?
+        )?;
+
+        // a batch pays a single account, so every nfnode must route host rewards the same way
+        let nfnode_payout_destination = PayoutConfig::destination_for(
+            payout_config_info,
+            &ClaimRole::Host,
+            &ctx.accounts.user.key()
+        )?;
+        if index == 0 {
+            payout_destination = nfnode_payout_destination;
+        }
+        require!(
+            nfnode_payout_destination == payout_destination,
+            RewardError::InvalidPayoutDestination
+        );
+
+        let mut nfnode_entry = Account::<NfNodeEntry>::try_from(nfnode_entry_info)?;
+        //validate if nft has valid mint authority, asset identities were validated at registration
+        if nfnode_entry.identity == NfNodeIdentity::Token2022 {
+            if nft_mint_account_info.owner != &ctx.accounts.token_program_2022.key() {
+                return err!(RewardError::InvalidNftMint);
+            }
+            verify_nfnode_mint(
+                nft_mint_account_info,
+                &admin_account.collection,
+                &nfnode_entry.nfnode_type,
+                ctx.accounts.mint_authority_entry.as_deref()
+            )?;
+        }
+
+        require!(nfnode_entry.host == ctx.accounts.user.key(), RewardError::UnauthorizedUser);
+        require!(nfnode_entry.role_deposit_covered(amount), RewardError::DepositRequired);
+        require!(
+            admin_account.claim_period_elapsed(
+                nfnode_entry.host_last_claimed_timestamp,
+                current_timestamp
+            ),
+            RewardError::ClaimAlreadyMadeToday
+        );
+        require!(nfnode_entry.device_online(current_timestamp), RewardError::DeviceOffline);
+        require!(!nfnode_entry.frozen, RewardError::NfNodeFrozen);
+        require!(!nfnode_entry.unbonding(), RewardError::NfNodeUnbonding);
+
+        // a short pool pays each nfnode its pro-rata share, the rest is owed on the host account
+        let reward_amount = reward_pool.record_shortfall(
+            &mut host_account.owed_amount,
+            reward_amount,
+            ctx.accounts.token_storage_account.amount - total_reward_amount
+        )?;
+
+        nfnode_entry.host_last_claimed_timestamp = current_timestamp;
+        nfnode_entry.total_rewards_claimed = nfnode_entry.total_rewards_claimed
+            .checked_add(reward_amount)
+            .ok_or(RewardError::ArithmeticOverflow)?;
+        // persist now so a duplicated nfnode in the same batch hits the cooldown check
+        nfnode_entry.exit(ctx.program_id)?;
+
+        total_reward_amount = total_reward_amount
+            .checked_add(reward_amount)
+            .ok_or(RewardError::ArithmeticOverflow)?;
+    }
+    require!(remaining_accounts.next().is_none(), RewardError::InvalidRemainingAccounts);
+
+    host_account.last_claimed_nonce = nonce;
+    host_account.last_claimed_timestamp = current_timestamp;
+    host_account.total_rewards_claimed = host_account.total_rewards_claimed
+        .checked_add(total_reward_amount)
+        .ok_or(RewardError::ArithmeticOverflow)?;
+
+    let recipient_token_account_info = match payout_destination {
+        Some(destination) => {
+            let recipient_token_account = ctx.accounts.recipient_token_account
+                .as_ref()
+                .ok_or(RewardError::InvalidPayoutDestination)?;
+            require!(
+                recipient_token_account.key() == destination ||
+                    recipient_token_account.owner == destination,
+                RewardError::InvalidPayoutDestination
+            );
+            BlacklistEntry::require_wallet_not_blacklisted(
+                &recipient_token_account.owner,
+                ctx.accounts.recipient_blacklist_entry
+                    .as_ref()
+                    .map(|recipient_blacklist_entry| recipient_blacklist_entry.as_ref())
+            )?;
+            recipient_token_account.to_account_info()
+        }
+        None => ctx.accounts.user_token_account.to_account_info(),
+    };
+
+    let authority_bump = ctx.bumps.token_storage_authority;
+    let authority_seeds = &[&b"token_storage"[..], &[authority_bump]];
+    let signer_seeds = &[&authority_seeds[..]];
+    // protocol fee and burn come off the top, the host is paid the rest
+    let total_reward_amount = collect_protocol_cut(
+        &mut ctx.accounts.reward_pool,
+        total_reward_amount,
+        ProtocolCutAccounts {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            token_storage_account: ctx.accounts.token_storage_account.to_account_info(),
+            token_storage_authority: ctx.accounts.token_storage_authority.to_account_info(),
+            treasury_token_account: ctx.accounts.treasury_token_account
+                .as_ref()
+                .map(|treasury_token_account| treasury_token_account.to_account_info()),
+        },
+        signer_seeds
+    )?;
+    if total_reward_amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.token_storage_account.to_account_info(),
+                    to: recipient_token_account_info,
+                    authority: ctx.accounts.token_storage_authority.to_account_info(),
+                },
+                signer_seeds
+            ),
+            total_reward_amount
+        )?;
+    }
+
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct HostClaimAll<'info> {
+    /// CHECK:
+    #[account(mut)]
+    pub user_admin: Signer<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"host", user.key().as_ref()],
+        bump,
+        constraint = host_account.host == user.key() @ RewardError::UnauthorizedUser
+    )]
+    pub host_account: Account<'info, Host>,
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+    /// CHECK:
+    #[account(mut, seeds = [b"token_storage"], bump)]
+    pub token_storage_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority,
+    )]
+    pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(mut, token::mint = token_mint)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = token_mint)]
+    pub recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: blacklist entry of the wallet owning recipient_token_account, checked by the handler
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
+    /// CHECK: blacklist entry of the host, only initialized for blacklisted wallets
+    #[account(seeds = [b"blacklist", user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    // authorization record of the mint authority shared by the batch, not needed for members of the
+    // nfnode collection. nfnodes from different mint authorities are claimed in separate batches
+    pub mint_authority_entry: Option<Account<'info, MintAuthorityEntry>>,
+    pub token_program_2022: Interface<'info, TokenInterface>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
+};
+use crate::{
+    errors::RewardError,
+    identity::verify_asset_owner,
+    state::{
+        NfNodeEntry,
+        AdminAccount,
+        IdentitySource,
+        NfNodeType,
+        CompressedNftProof,
+        PAUSE_REGISTRATION,
+    },
+};
+
+pub fn initialize_asset_nfnode<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitializeAssetNfNode<'info>>,
+    host_share: u64,
+    nfnode_type: NfNodeType,
+    compressed_nft_proof: Option<CompressedNftProof>
+) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(
+        ctx.accounts.user_admin.key() == admin_account.admin_pubkey,
+        RewardError::UnauthorizedAdmin
+    );
+    require!(
+        !admin_account.is_paused(PAUSE_REGISTRATION, Clock::get()?.unix_timestamp),
+        RewardError::ProgramPaused
+    );
+    let valid_mint = admin_account.valid_mint;
+    let token_mint = &ctx.accounts.token_mint;
+    require!(valid_mint == token_mint.key(), RewardError::InvalidMint);
+    let user_admin_account_info = ctx.accounts.user_admin.to_account_info();
+    let is_partially_signed_by_admin = user_admin_account_info.is_signer;
+    require!(is_partially_signed_by_admin, RewardError::MissingAdminSignature);
+
+    //validate the user owns the asset and it comes from an official collection or tree
+    let identity_source = &ctx.accounts.identity_source;
+    verify_asset_owner(
+        identity_source,
+        &ctx.accounts.asset.to_account_info(),
+        ctx.accounts.merkle_tree.as_ref().map(|merkle_tree| merkle_tree.as_ref()),
+        ctx.accounts.compression_program.as_ref().map(|program| program.as_ref()),
+        ctx.remaining_accounts,
+        compressed_nft_proof.as_ref(),
+        &ctx.accounts.user.key()
+    )?;
+
+    //deposit 5000 WAYRU tokens if the type is not DON
+    let amount = 5000000000;
+    if nfnode_type != NfNodeType::DON {
+        token::transfer(ctx.accounts.transfer_to_token_storage(), amount)?;
+    }
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    nfnode_entry.version = NfNodeEntry::VERSION;
+    nfnode_entry.identity = identity_source.identity;
+    nfnode_entry.host = ctx.accounts.host.key();
+    nfnode_entry.host_share = host_share;
+    nfnode_entry.manufacturer = ctx.accounts.manufacturer.key();
+    nfnode_entry.owner_last_claimed_timestamp = 0;
+    nfnode_entry.host_last_claimed_timestamp = 0;
+    nfnode_entry.manufacturer_last_claimed_timestamp = 0;
+    nfnode_entry.total_rewards_claimed = 0;
+
+    if nfnode_type != NfNodeType::DON {
+        nfnode_entry.nfnode_type = nfnode_type;
+        nfnode_entry.deposit_amount = amount;
+        nfnode_entry.deposit_timestamp = Clock::get()?.unix_timestamp;
+    }
+    nfnode_entry.depositor = ctx.accounts.user.key();
+
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct InitializeAssetNfNode<'info> {
+    #[account(mut)]
+    pub user_admin: Signer<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    ///CHECK: only read account
+    pub host: AccountInfo<'info>,
+    ///CHECK: only read account
+    pub manufacturer: AccountInfo<'info>,
+    ///CHECK: only read account
+    pub token_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Core asset account or compressed nft asset id, validated by the handler
+    pub asset: UncheckedAccount<'info>,
+    pub identity_source: Box<Account<'info, IdentitySource>>,
+    /// CHECK: Bubblegum merkle tree, required for compressed nfts
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: spl account compression program, required for compressed nfts
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + NfNodeEntry::INIT_SPACE,
+        seeds = [b"nfnode_entry", asset.key().as_ref()],
+        bump
+    )]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+    /// CHECK: only read account
+    #[account(mut, seeds = [b"token_storage", asset.key().as_ref()], bump)]
+    pub token_storage_authority: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_storage_authority
+    )]
+    pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+impl<'info> InitializeAssetNfNode<'info> {
+    fn transfer_to_token_storage(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(self.token_program.to_account_info(), Transfer {
+            from: self.user_token_account.to_account_info(),
+            to: self.token_storage_account.to_account_info(),
+            authority: self.user.to_account_info(),
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Host;
+pub fn initialize_host(ctx: Context<InitializeHost>) -> Result<()> {
+    let host_account = &mut ctx.accounts.host_account;
+    host_account.version = Host::VERSION;
+    host_account.host = ctx.accounts.user.key();
+    host_account.last_claimed_nonce = 0;
+    host_account.last_claimed_timestamp = 0;
+    host_account.total_rewards_claimed = 0;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct InitializeHost<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Host::INIT_SPACE,
+        seeds = [b"host", user.key().as_ref()],
+        bump
+    )]
+    pub host_account: Account<'info, Host>,
+    pub system_program: Program<'info, System>,
+}
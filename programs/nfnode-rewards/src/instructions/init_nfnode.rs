@@ -4,18 +4,30 @@ use anchor_spl::{
     token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
     token_interface::{ Mint as Mint2022, TokenInterface, TokenAccount as SplToken2022Account },
 };
-use crate::{ errors::RewardError, state::{ NfNodeEntry, AdminAccount, NfNodeType } };
+use crate::{ collection::verify_collection_member, errors::RewardError, state::{
+    NfNodeEntry,
+    AdminAccount,
+    MintAuthorityEntry,
+    NfNodeType,
+    AutoCompound,
+    PAUSE_REGISTRATION,
+} };
 
 pub fn initialize_nfnode(
     ctx: Context<InitializeNfNode>,
     host_share: u64,
-    nfnode_type: NfNodeType
+    nfnode_type: NfNodeType,
+    debt_withholding_bps: Option<u16>
 ) -> Result<()> {
     let admin_account = &ctx.accounts.admin_account;
     require!(
         ctx.accounts.user_admin.key() == admin_account.admin_pubkey,
         RewardError::UnauthorizedAdmin
     );
+    require!(
+        !admin_account.is_paused(PAUSE_REGISTRATION, Clock::get()?.unix_timestamp),
+        RewardError::ProgramPaused
+    );
     let valid_mint = admin_account.valid_mint;
     let token_mint = &ctx.accounts.token_mint;
     require!(valid_mint == token_mint.key(), RewardError::InvalidMint);
@@ -62,22 +74,57 @@ pub fn initialize_nfnode(
     if user_nft_token_account.mint != ctx.accounts.nft_mint_address.key() {
         return err!(RewardError::InvalidNftMint);
     }
-    //validate if nft has valid mint authority
     let metadata_account_info = &ctx.accounts.nft_mint_address.to_account_info();
+    if admin_account.collection != Pubkey::default() {
+        verify_collection_member(metadata_account_info, &admin_account.collection, &nfnode_type)?;
+    }
+    //validate if nft has valid mint authority, a revoked one is accepted for members of the nfnode collection
     let metadata_account_data = &metadata_account_info.try_borrow_data()?;
     let mint = &Mint2022::try_deserialize(&mut &metadata_account_data[..])?;
-    let mint_authority = mint.mint_authority.unwrap();
-    require!(
-        admin_account.mint_authorities.contains(&mint_authority),
-        RewardError::UnauthorizedMintAuthority
-    );
+    match Option::<Pubkey>::from(mint.mint_authority) {
+        Some(mint_authority) => {
+            let mint_authority_entry = ctx.accounts.mint_authority_entry
+                .as_ref()
+                .ok_or(RewardError::UnauthorizedMintAuthority)?;
+            require!(
+                mint_authority_entry.authority == mint_authority,
+                RewardError::UnauthorizedMintAuthority
+            );
+            // the grant window bounds registration, the mint itself carries no creation time
+            mint_authority_entry.check_registration_grant(&nfnode_type, Clock::get()?.unix_timestamp)?;
+        }
+        None =>
+            require!(
+                admin_account.collection != Pubkey::default(),
+                RewardError::UnauthorizedMintAuthority
+            ),
+    }
 
-    //deposit 5000 WAYRU tokens if the type is not DON
+    //deposit 5000 WAYRU tokens if the type is not DON, auto-compounding nodes fill it from claims instead
+    //and admin approved financed nodes repay it from claims as a debt
     let amount = 5000000000;
-    if nfnode_type != NfNodeType::DON {
+    let auto_compound = AutoCompound::load_for(
+        &ctx.accounts.auto_compound.to_account_info(),
+        &ctx.accounts.user.key()
+    )?;
+    if let Some(debt_withholding_bps) = debt_withholding_bps {
+        require!(nfnode_type != NfNodeType::DON, RewardError::InvalidDebtWithholding);
+        require!(
+            debt_withholding_bps > 0 && debt_withholding_bps <= 10000,
+            RewardError::InvalidDebtWithholding
+        );
+    }
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let compound_waiver = auto_compound
+        .as_ref()
+        .is_some_and(|auto_compound| auto_compound.waives_deposit(current_timestamp));
+    let upfront_deposit =
+        nfnode_type != NfNodeType::DON && !compound_waiver && debt_withholding_bps.is_none();
+    if upfront_deposit {
         token::transfer(ctx.accounts.transfer_to_token_storage(), amount)?;
     }
     let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    nfnode_entry.version = NfNodeEntry::VERSION;
     nfnode_entry.host = ctx.accounts.host.key();
     nfnode_entry.host_share = host_share;
     nfnode_entry.manufacturer = ctx.accounts.manufacturer.key();
@@ -89,9 +136,18 @@ pub fn initialize_nfnode(
 
     if nfnode_type != NfNodeType::DON {
         nfnode_entry.nfnode_type = nfnode_type;
+    }
+    if upfront_deposit {
         nfnode_entry.deposit_amount = amount;
         nfnode_entry.deposit_timestamp = Clock::get()?.unix_timestamp;
     }
+    // an upfront deposit stays with the user, one filled from owner claims follows the nft
+    nfnode_entry.depositor = ctx.accounts.user.key();
+    nfnode_entry.deposit_carries_with_nft = !upfront_deposit;
+    if let Some(debt_withholding_bps) = debt_withholding_bps {
+        nfnode_entry.deposit_debt = amount;
+        nfnode_entry.debt_withholding_bps = debt_withholding_bps;
+    }
 
     Ok(())
 }
@@ -114,13 +170,18 @@ pub struct InitializeNfNode<'info> {
     #[account(
         init,
         payer = user,
-        space = 8 + std::mem::size_of::<NfNodeEntry>(),
+        space = 8 + NfNodeEntry::INIT_SPACE,
         seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()],
         bump
     )]
     pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
     #[account(seeds = [b"admin_account"], bump)]
     pub admin_account: Box<Account<'info, AdminAccount>>,
+    // authorization record of the nft mint authority, not needed for members of the nfnode collection
+    pub mint_authority_entry: Option<Box<Account<'info, MintAuthorityEntry>>>,
+    /// CHECK: auto-compound setting set by set_auto_compound, may be uninitialized
+    #[account(seeds = [b"auto_compound", nft_mint_address.key().as_ref()], bump)]
+    pub auto_compound: UncheckedAccount<'info>,
     /// CHECK: only read account
     #[account(mut, seeds = [b"token_storage",nft_mint_address.key().as_ref()], bump)]
     pub token_storage_authority: AccountInfo<'info>,
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ Mint, TokenAccount };
+
+use crate::{
+    errors::RewardError,
+    state::{ AdminAccount, RewardPool, MAX_PROTOCOL_CUT_BPS },
+};
+pub fn initialize_reward_pool(
+    ctx: Context<InitializeRewardPool>,
+    protocol_fee_bps: u16,
+    burn_bps: u16
+) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.version = RewardPool::VERSION;
+    set_protocol_cut(
+        reward_pool,
+        protocol_fee_bps,
+        burn_bps,
+        ctx.accounts.treasury_token_account.as_ref()
+    )
+}
+pub fn set_reward_pool_fees(
+    ctx: Context<SetRewardPoolFees>,
+    protocol_fee_bps: u16,
+    burn_bps: u16
+) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+    set_protocol_cut(
+        &mut ctx.accounts.reward_pool,
+        protocol_fee_bps,
+        burn_bps,
+        ctx.accounts.treasury_token_account.as_ref()
+    )
+}
+// a treasury token account is only needed while a protocol fee is charged
+fn set_protocol_cut(
+    reward_pool: &mut RewardPool,
+    protocol_fee_bps: u16,
+    burn_bps: u16,
+    treasury_token_account: Option<&Account<TokenAccount>>
+) -> Result<()> {
+    require!(
+        protocol_fee_bps.checked_add(burn_bps).is_some_and(|bps| bps <= MAX_PROTOCOL_CUT_BPS),
+        RewardError::InvalidProtocolCut
+    );
+    reward_pool.treasury_token_account = match treasury_token_account {
+        Some(treasury_token_account) => treasury_token_account.key(),
+        None => {
+            require!(protocol_fee_bps == 0, RewardError::InvalidTreasuryTokenAccount);
+            Pubkey::default()
+        }
+    };
+    reward_pool.protocol_fee_bps = protocol_fee_bps;
+    reward_pool.burn_bps = burn_bps;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct InitializeRewardPool<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + RewardPool::INIT_SPACE,
+        seeds = [b"reward_pool"],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+    pub token_mint: Account<'info, Mint>,
+    #[account(token::mint = token_mint)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    pub system_program: Program<'info, System>,
+}
+#[derive(Accounts)]
+pub struct SetRewardPoolFees<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    pub token_mint: Account<'info, Mint>,
+    #[account(token::mint = token_mint)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+}
@@ -1,5 +1,5 @@
 use crate::{
-    state::{AdminAccount, MAX_MINT_AUTHORITIES},
+    state::{ AdminAccount, MintAuthorityEntry, NfNodeType },
     NfnodeRewards,
 };
 use anchor_lang::prelude::*;
@@ -9,12 +9,14 @@ use anchor_spl::{
 
 pub fn initialize_system(ctx: Context<InitializeSystem>) -> Result<()> {
     let admin_account = &mut ctx.accounts.admin_account;
+    admin_account.version = AdminAccount::VERSION;
     admin_account.admin_pubkey = ctx.accounts.user.key();
-    admin_account.paused = false;
+    admin_account.pause_flags = 0;
     admin_account.valid_mint = ctx.accounts.token_mint.key();
-    admin_account
-        .mint_authorities
-        .push(ctx.accounts.mint_authority.key());
+    let mint_authority_entry = &mut ctx.accounts.mint_authority_entry;
+    mint_authority_entry.version = MintAuthorityEntry::VERSION;
+    mint_authority_entry.authority = ctx.accounts.mint_authority.key();
+    mint_authority_entry.allowed_nfnode_types = NfNodeType::ALL_FLAGS;
     Ok(())
 }
 
@@ -25,9 +27,7 @@ pub struct InitializeSystem<'info> {
     #[account(
         init,
         payer = user,
-        space = 8 +
-        std::mem::size_of::<AdminAccount>() +
-        MAX_MINT_AUTHORITIES * std::mem::size_of::<Pubkey>(),
+        space = 8 + AdminAccount::INIT_SPACE,
         seeds = [b"admin_account"],
         bump
     )]
@@ -35,6 +35,14 @@ pub struct InitializeSystem<'info> {
     ///CHECK: only read account
     #[account(constraint = program_data.upgrade_authority_address == Some(mint_authority.key()))]
     pub mint_authority: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + MintAuthorityEntry::INIT_SPACE,
+        seeds = [b"mint_authority", mint_authority.key().as_ref()],
+        bump
+    )]
+    pub mint_authority_entry: Account<'info, MintAuthorityEntry>,
     ///CHECK: only read account
     pub token_mint: Account<'info, Mint>,
     pub token_program: Program<'info, Token>,
@@ -0,0 +1,186 @@
+use anchor_lang::{ prelude::*, system_program, Discriminator };
+
+use crate::{
+    errors::RewardError,
+    state::{
+        AdminAccount,
+        ClaimPeriodMode,
+        MintAuthorityEntry,
+        NfNodeType,
+        PAUSE_ALL,
+        realloc_for_migration,
+    },
+};
+// AdminAccount layout before mint authorities moved to their own accounts
+#[derive(AnchorDeserialize)]
+struct AdminAccountLegacy {
+    admin_pubkey: Pubkey,
+    admin_candidate_pubkey: Pubkey,
+    paused: bool,
+    admin_update_requested: bool,
+    valid_mint: Pubkey,
+    mint_authorities: Vec<Pubkey>,
+}
+// version 1 prefixed the legacy layout with a version byte and was sized for 10 mint authorities
+const ADMIN_ACCOUNT_V1_LEN: usize = 8 + 1 + 32 + 32 + 1 + 1 + 32 + 4 + 10 * 32 + 64;
+// AdminAccount layout before the pause timestamp and emergency withdrawal grace period
+#[derive(AnchorDeserialize)]
+struct AdminAccountV2 {
+    version: u8,
+    admin_pubkey: Pubkey,
+    admin_candidate_pubkey: Pubkey,
+    paused: bool,
+    admin_update_requested: bool,
+    valid_mint: Pubkey,
+    collection: Pubkey,
+    claim_period_mode: ClaimPeriodMode,
+    claim_period_seconds: i64,
+    claim_period_offset: i64,
+}
+const ADMIN_ACCOUNT_V2_LEN: usize = 8 + 1 + 32 + 32 + 1 + 1 + 32 + 32 + 1 + 8 + 8 + 15;
+pub fn migrate_admin_account<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateAdminAccount<'info>>
+) -> Result<()> {
+    let admin_account_info = ctx.accounts.admin_account.to_account_info();
+    require_keys_eq!(*admin_account_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let (admin_account, mint_authorities) = {
+        let admin_account_data = admin_account_info.try_borrow_data()?;
+        require!(
+            admin_account_data.len() >= 8 && admin_account_data[..8] == AdminAccount::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        require!(
+            admin_account_data.len() != 8 + AdminAccount::INIT_SPACE ||
+                admin_account_data[8] != AdminAccount::VERSION,
+            RewardError::AlreadyMigrated
+        );
+        let mut admin_account = AdminAccount {
+            version: AdminAccount::VERSION,
+            admin_pubkey: Pubkey::default(),
+            admin_candidate_pubkey: Pubkey::default(),
+            pause_flags: 0,
+            admin_update_requested: false,
+            valid_mint: Pubkey::default(),
+            collection: Pubkey::default(),
+            claim_period_mode: ClaimPeriodMode::CalendarAligned,
+            claim_period_seconds: 0,
+            claim_period_offset: 0,
+            paused_at: 0,
+            emergency_withdrawal_grace_period: 0,
+            pause_reason: 0,
+            pause_expires_at: 0,
+            reserved: [0; 54],
+        };
+        let (paused, mint_authorities) = if admin_account_data.len() == 8 + AdminAccount::INIT_SPACE {
+            // version 3 has the current layout with a paused bool in place of the pause flags
+            let v3 = AdminAccount::try_deserialize(&mut &admin_account_data[..])?;
+            let paused = v3.pause_flags != 0;
+            admin_account = AdminAccount { version: AdminAccount::VERSION, ..v3 };
+            (paused, vec![])
+        } else if admin_account_data.len() == ADMIN_ACCOUNT_V2_LEN {
+            let v2 = AdminAccountV2::deserialize(&mut &admin_account_data[8..])?;
+            require!(v2.version == 2, RewardError::AlreadyMigrated);
+            admin_account.admin_pubkey = v2.admin_pubkey;
+            admin_account.admin_candidate_pubkey = v2.admin_candidate_pubkey;
+            admin_account.admin_update_requested = v2.admin_update_requested;
+            admin_account.valid_mint = v2.valid_mint;
+            admin_account.collection = v2.collection;
+            admin_account.claim_period_mode = v2.claim_period_mode;
+            admin_account.claim_period_seconds = v2.claim_period_seconds;
+            admin_account.claim_period_offset = v2.claim_period_offset;
+            // mint authorities already have their own entries
+            (v2.paused, vec![])
+        } else {
+            let legacy_offset = if admin_account_data.len() == ADMIN_ACCOUNT_V1_LEN { 9 } else { 8 };
+            let legacy = AdminAccountLegacy::deserialize(&mut &admin_account_data[legacy_offset..])?;
+            admin_account.admin_pubkey = legacy.admin_pubkey;
+            admin_account.admin_candidate_pubkey = legacy.admin_candidate_pubkey;
+            admin_account.admin_update_requested = legacy.admin_update_requested;
+            admin_account.valid_mint = legacy.valid_mint;
+            (legacy.paused, legacy.mint_authorities)
+        };
+        // a pause from before the upgrade pauses every subsystem,
+        // its grace period starts now unless it was already recorded
+        if paused {
+            admin_account.pause_flags = PAUSE_ALL;
+            if admin_account.paused_at == 0 {
+                admin_account.paused_at = current_timestamp;
+            }
+        }
+        (admin_account, mint_authorities)
+    };
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+
+    // every legacy mint authority gets an unrestricted MintAuthorityEntry,
+    // passed in the same order in remaining accounts
+    require!(
+        ctx.remaining_accounts.len() == mint_authorities.len(),
+        RewardError::InvalidRemainingAccounts
+    );
+    let rent = Rent::get()?;
+    let mint_authority_entry_len = 8 + MintAuthorityEntry::INIT_SPACE;
+    for (mint_authority, mint_authority_entry_info) in mint_authorities
+        .iter()
+        .zip(ctx.remaining_accounts.iter()) {
+        let (derived_mint_authority_entry, bump) = Pubkey::find_program_address(
+            &[b"mint_authority", mint_authority.as_ref()],
+            ctx.program_id
+        );
+        require!(
+            derived_mint_authority_entry == *mint_authority_entry_info.key,
+            RewardError::InvalidRemainingAccounts
+        );
+        require!(mint_authority_entry_info.is_writable, RewardError::InvalidRemainingAccounts);
+        require!(
+            mint_authority_entry_info.data_is_empty(),
+            RewardError::MintAuthorityAlreadyExists
+        );
+        let authority_seeds = &[&b"mint_authority"[..], mint_authority.as_ref(), &[bump]];
+        let signer_seeds = &[&authority_seeds[..]];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: mint_authority_entry_info.clone(),
+                },
+                signer_seeds
+            ),
+            rent.minimum_balance(mint_authority_entry_len),
+            mint_authority_entry_len as u64,
+            ctx.program_id
+        )?;
+        let mint_authority_entry = MintAuthorityEntry {
+            version: MintAuthorityEntry::VERSION,
+            authority: *mint_authority,
+            registration_opens_at: 0,
+            registration_closes_at: 0,
+            allowed_nfnode_types: NfNodeType::ALL_FLAGS,
+            reserved: [0; 15],
+        };
+        let mut mint_authority_entry_data = mint_authority_entry_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut mint_authority_entry_data[..];
+        mint_authority_entry.try_serialize(&mut writer)?;
+    }
+
+    realloc_for_migration(
+        &admin_account_info,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + AdminAccount::INIT_SPACE
+    )?;
+    let mut admin_account_data = admin_account_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut admin_account_data[..];
+    admin_account.try_serialize(&mut writer)?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct MigrateAdminAccount<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: legacy layout, validated and rewritten by the handler
+    #[account(mut, seeds = [b"admin_account"], bump)]
+    pub admin_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,58 @@
+use anchor_lang::{ prelude::*, Discriminator };
+
+use crate::{ errors::RewardError, state::{ AutoCompound, realloc_for_migration } };
+// AutoCompound layout before versioning
+#[derive(AnchorDeserialize)]
+struct AutoCompoundLegacy {
+    owner: Pubkey,
+    compound_bps: u16,
+    stake_beyond_requirement: bool,
+}
+pub fn migrate_auto_compound(ctx: Context<MigrateAutoCompound>) -> Result<()> {
+    let auto_compound_info = ctx.accounts.auto_compound.to_account_info();
+    require_keys_eq!(*auto_compound_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let auto_compound = {
+        let auto_compound_data = auto_compound_info.try_borrow_data()?;
+        require!(
+            auto_compound_data.len() >= 8 && auto_compound_data[..8] == AutoCompound::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        // legacy accounts have no version byte and are smaller than the current layout
+        require!(
+            auto_compound_data.len() < 8 + AutoCompound::INIT_SPACE,
+            RewardError::AlreadyMigrated
+        );
+        let legacy = AutoCompoundLegacy::deserialize(&mut &auto_compound_data[8..])?;
+        AutoCompound {
+            version: AutoCompound::VERSION,
+            owner: legacy.owner,
+            compound_bps: legacy.compound_bps,
+            stake_beyond_requirement: legacy.stake_beyond_requirement,
+            // legacy settings never started a coverage period
+            enabled_at: 0,
+            reserved: [0; 24],
+        }
+    };
+
+    realloc_for_migration(
+        &auto_compound_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + AutoCompound::INIT_SPACE
+    )?;
+    let mut auto_compound_data = auto_compound_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut auto_compound_data[..];
+    auto_compound.try_serialize(&mut writer)?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct MigrateAutoCompound<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: only used to derive the auto-compound setting
+    pub nft_mint_address: UncheckedAccount<'info>,
+    /// CHECK: legacy layout, validated and rewritten by the handler
+    #[account(mut, seeds = [b"auto_compound", nft_mint_address.key().as_ref()], bump)]
+    pub auto_compound: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,56 @@
+use anchor_lang::{ prelude::*, Discriminator };
+
+use crate::{ errors::RewardError, state::{ Host, realloc_for_migration } };
+// Host layout before versioning
+#[derive(AnchorDeserialize)]
+struct HostLegacy {
+    host: Pubkey,
+    last_claimed_nonce: u64,
+    last_claimed_timestamp: i64,
+    total_rewards_claimed: u64,
+}
+pub fn migrate_host(ctx: Context<MigrateHost>) -> Result<()> {
+    let host_account_info = ctx.accounts.host_account.to_account_info();
+    require_keys_eq!(*host_account_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let host_account = {
+        let host_account_data = host_account_info.try_borrow_data()?;
+        require!(
+            host_account_data.len() >= 8 && host_account_data[..8] == Host::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        // legacy accounts have no version byte and are smaller than the current layout
+        require!(host_account_data.len() < 8 + Host::INIT_SPACE, RewardError::AlreadyMigrated);
+        let legacy = HostLegacy::deserialize(&mut &host_account_data[8..])?;
+        Host {
+            version: Host::VERSION,
+            host: legacy.host,
+            last_claimed_nonce: legacy.last_claimed_nonce,
+            last_claimed_timestamp: legacy.last_claimed_timestamp,
+            total_rewards_claimed: legacy.total_rewards_claimed,
+            owed_amount: 0,
+            reserved: [0; 24],
+        }
+    };
+
+    realloc_for_migration(
+        &host_account_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + Host::INIT_SPACE
+    )?;
+    let mut host_account_data = host_account_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut host_account_data[..];
+    host_account.try_serialize(&mut writer)?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct MigrateHost<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: only used to derive the host account
+    pub host: UncheckedAccount<'info>,
+    /// CHECK: legacy layout, validated and rewritten by the handler
+    #[account(mut, seeds = [b"host", host.key().as_ref()], bump)]
+    pub host_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
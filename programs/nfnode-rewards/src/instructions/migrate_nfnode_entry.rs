@@ -0,0 +1,110 @@
+use anchor_lang::{ prelude::*, Discriminator };
+
+use crate::{
+    errors::RewardError,
+    state::{ LockupTier, NfNodeEntry, NfNodeIdentity, NfNodeType, realloc_for_migration },
+};
+// NfNodeEntry layout before versioning
+#[derive(AnchorDeserialize)]
+struct NfNodeEntryLegacy {
+    owner_last_claimed_timestamp: i64,
+    host: Pubkey,
+    host_share: u64,
+    host_last_claimed_timestamp: i64,
+    manufacturer: Pubkey,
+    manufacturer_last_claimed_timestamp: i64,
+    total_rewards_claimed: u64,
+    deposit_amount: u64,
+    deposit_timestamp: i64,
+    nfnode_type: NfNodeType,
+}
+// version 1 ended in 10 reserved bytes where the depositor record now starts
+const NFNODE_ENTRY_V1_LEN: usize = 8 + NfNodeEntry::INIT_SPACE - (32 + 1 + 32) + 10;
+pub fn migrate_nfnode_entry(ctx: Context<MigrateNfNodeEntry>) -> Result<()> {
+    let nfnode_entry_info = ctx.accounts.nfnode_entry.to_account_info();
+    require_keys_eq!(*nfnode_entry_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let nfnode_entry = {
+        let nfnode_entry_data = nfnode_entry_info.try_borrow_data()?;
+        require!(
+            nfnode_entry_data.len() >= 8 && nfnode_entry_data[..8] == NfNodeEntry::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        require!(
+            nfnode_entry_data.len() < 8 + NfNodeEntry::INIT_SPACE,
+            RewardError::AlreadyMigrated
+        );
+        if nfnode_entry_data.len() == NFNODE_ENTRY_V1_LEN && nfnode_entry_data[8] == 1 {
+            // version 1 is a prefix of the current layout, the zeroed tail reads as an empty record
+            let mut v1_data = nfnode_entry_data.to_vec();
+            v1_data.resize(8 + NfNodeEntry::INIT_SPACE, 0);
+            let v1 = NfNodeEntry::try_deserialize(&mut &v1_data[..])?;
+            // deposits made before the record keep following the nft
+            NfNodeEntry {
+                version: NfNodeEntry::VERSION,
+                deposit_carries_with_nft: true,
+                ..v1
+            }
+        } else {
+            // legacy accounts have no version byte and are smaller than version 1
+            legacy_nfnode_entry(&nfnode_entry_data)?
+        }
+    };
+
+    realloc_for_migration(
+        &nfnode_entry_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + NfNodeEntry::INIT_SPACE
+    )?;
+    let mut nfnode_entry_data = nfnode_entry_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut nfnode_entry_data[..];
+    nfnode_entry.try_serialize(&mut writer)?;
+    Ok(())
+}
+fn legacy_nfnode_entry(nfnode_entry_data: &[u8]) -> Result<NfNodeEntry> {
+    let mut legacy_data = &nfnode_entry_data[8..];
+    let legacy = NfNodeEntryLegacy::deserialize(&mut legacy_data)?;
+    // deposit financing fields were appended before versioning, older accounts only have padding
+    let (deposit_debt, debt_withholding_bps) = if legacy_data.len() >= 10 {
+        (u64::deserialize(&mut legacy_data)?, u16::deserialize(&mut legacy_data)?)
+    } else {
+        (0, 0)
+    };
+    Ok(NfNodeEntry {
+        version: NfNodeEntry::VERSION,
+        owner_last_claimed_timestamp: legacy.owner_last_claimed_timestamp,
+        host: legacy.host,
+        host_share: legacy.host_share,
+        host_last_claimed_timestamp: legacy.host_last_claimed_timestamp,
+        manufacturer: legacy.manufacturer,
+        manufacturer_last_claimed_timestamp: legacy.manufacturer_last_claimed_timestamp,
+        total_rewards_claimed: legacy.total_rewards_claimed,
+        deposit_amount: legacy.deposit_amount,
+        deposit_timestamp: legacy.deposit_timestamp,
+        nfnode_type: legacy.nfnode_type,
+        deposit_debt,
+        debt_withholding_bps,
+        identity: NfNodeIdentity::Token2022,
+        device_pubkey: Pubkey::default(),
+        last_heartbeat_timestamp: 0,
+        frozen: false,
+        freeze_reason: 0,
+        freeze_appealed: false,
+        lockup_tier: LockupTier::Days30,
+        unbonding_started_at: 0,
+        depositor: Pubkey::default(),
+        deposit_carries_with_nft: true,
+        reserved: [0; 32],
+    })
+}
+#[derive(Accounts)]
+pub struct MigrateNfNodeEntry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: only used to derive the nfnode entry
+    pub nft_mint_address: UncheckedAccount<'info>,
+    /// CHECK: legacy layout, validated and rewritten by the handler
+    #[account(mut, seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
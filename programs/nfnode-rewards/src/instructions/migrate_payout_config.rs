@@ -0,0 +1,56 @@
+use anchor_lang::{ prelude::*, Discriminator };
+
+use crate::{ errors::RewardError, state::{ PayoutConfig, PayoutRoute, realloc_for_migration } };
+// PayoutConfig layout before versioning
+#[derive(AnchorDeserialize)]
+struct PayoutConfigLegacy {
+    owner: PayoutRoute,
+    host: PayoutRoute,
+    manufacturer: PayoutRoute,
+}
+pub fn migrate_payout_config(ctx: Context<MigratePayoutConfig>) -> Result<()> {
+    let payout_config_info = ctx.accounts.payout_config.to_account_info();
+    require_keys_eq!(*payout_config_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let payout_config = {
+        let payout_config_data = payout_config_info.try_borrow_data()?;
+        require!(
+            payout_config_data.len() >= 8 && payout_config_data[..8] == PayoutConfig::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        // legacy accounts have no version byte and are smaller than the current layout
+        require!(
+            payout_config_data.len() < 8 + PayoutConfig::INIT_SPACE,
+            RewardError::AlreadyMigrated
+        );
+        let legacy = PayoutConfigLegacy::deserialize(&mut &payout_config_data[8..])?;
+        PayoutConfig {
+            version: PayoutConfig::VERSION,
+            owner: legacy.owner,
+            host: legacy.host,
+            manufacturer: legacy.manufacturer,
+            reserved: [0; 32],
+        }
+    };
+
+    realloc_for_migration(
+        &payout_config_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + PayoutConfig::INIT_SPACE
+    )?;
+    let mut payout_config_data = payout_config_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut payout_config_data[..];
+    payout_config.try_serialize(&mut writer)?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct MigratePayoutConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: only used to derive the payout config
+    pub nft_mint_address: UncheckedAccount<'info>,
+    /// CHECK: legacy layout, validated and rewritten by the handler
+    #[account(mut, seeds = [b"payout_config", nft_mint_address.key().as_ref()], bump)]
+    pub payout_config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
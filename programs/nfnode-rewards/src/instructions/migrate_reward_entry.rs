@@ -0,0 +1,65 @@
+use anchor_lang::{ prelude::*, Discriminator };
+
+use crate::{ errors::RewardError, state::{ RewardEntry, realloc_for_migration } };
+// RewardEntry layout before versioning
+#[derive(AnchorDeserialize)]
+struct RewardEntryLegacy {
+    last_claimed_nonce: u64,
+    last_claimed_timestamp: i64,
+    total_rewards_earned: u64,
+}
+pub fn migrate_reward_entry(ctx: Context<MigrateRewardEntry>) -> Result<()> {
+    let reward_entry_info = ctx.accounts.reward_entry.to_account_info();
+    require_keys_eq!(*reward_entry_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let reward_entry = {
+        let reward_entry_data = reward_entry_info.try_borrow_data()?;
+        require!(
+            reward_entry_data.len() >= 8 && reward_entry_data[..8] == RewardEntry::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        // legacy accounts have no version byte and are smaller than the current layout
+        require!(
+            reward_entry_data.len() < 8 + RewardEntry::INIT_SPACE,
+            RewardError::AlreadyMigrated
+        );
+        let legacy = RewardEntryLegacy::deserialize(&mut &reward_entry_data[8..])?;
+        RewardEntry {
+            version: RewardEntry::VERSION,
+            last_claimed_nonce: legacy.last_claimed_nonce,
+            last_claimed_timestamp: legacy.last_claimed_timestamp,
+            total_rewards_earned: legacy.total_rewards_earned,
+            owed_amount: 0,
+            owed_host_amount: 0,
+            owed_manufacturer_amount: 0,
+            reserved: [0; 8],
+        }
+    };
+
+    realloc_for_migration(
+        &reward_entry_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + RewardEntry::INIT_SPACE
+    )?;
+    let mut reward_entry_data = reward_entry_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut reward_entry_data[..];
+    reward_entry.try_serialize(&mut writer)?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct MigrateRewardEntry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: only used to derive the reward entry
+    pub user: UncheckedAccount<'info>,
+    /// CHECK: only used to derive the reward entry
+    pub nft_mint_address: UncheckedAccount<'info>,
+    /// CHECK: legacy layout, validated and rewritten by the handler
+    #[account(
+        mut,
+        seeds = [b"reward_entry", user.key().as_ref(), nft_mint_address.key().as_ref()],
+        bump
+    )]
+    pub reward_entry: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
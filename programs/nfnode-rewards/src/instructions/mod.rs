@@ -8,6 +8,58 @@ mod deposit_tokens;
 mod withdraw_tokens;
 mod add_mint_authority;
 mod remove_mint_authority;
+mod init_host;
+mod host_claim_all;
+mod set_payout_destination;
+mod crank_claim_rewards;
+mod set_auto_compound;
+mod migrate_admin_account;
+mod migrate_nfnode_entry;
+mod migrate_reward_entry;
+mod migrate_host;
+mod migrate_payout_config;
+mod migrate_auto_compound;
+mod set_mint_authority_grant;
+mod set_nfnode_collection;
+mod add_identity_source;
+mod remove_identity_source;
+mod init_asset_nfnode;
+mod asset_owner_claim_rewards;
+mod asset_deposit_tokens;
+mod asset_withdraw_tokens;
+mod asset_set_payout_destination;
+mod asset_set_auto_compound;
+mod asset_set_stake_commission;
+mod asset_set_deposit_carry;
+mod asset_request_withdrawal;
+mod asset_complete_withdrawal;
+mod asset_crank_claim_rewards;
+mod bind_device;
+mod submit_heartbeat;
+mod assert_location;
+mod set_h3_region_multiplier;
+mod set_claim_period;
+mod set_emergency_withdrawal_grace_period;
+mod set_pause_flags;
+mod freeze_nfnode;
+mod appeal_nfnode_freeze;
+mod blacklist_wallet;
+mod remove_blacklisted_wallet;
+mod appeal_blacklist;
+mod init_reward_pool;
+mod create_reward_stream;
+mod drip;
+mod close_reward_stream;
+mod set_reserve_ratio;
+mod claim_owed;
+mod set_stake_commission;
+mod stake;
+mod unstake;
+mod withdraw_unstaked;
+mod claim_stake_rewards;
+mod request_withdrawal;
+mod complete_withdrawal;
+mod set_deposit_carry;
 
 pub use add_mint_authority::*;
 pub use remove_mint_authority::*;
@@ -18,4 +70,56 @@ pub use update_nfnode::*;
 pub use owner_claim_rewards::*;
 pub use others_claim_rewards::*;
 pub use deposit_tokens::*;
-pub use withdraw_tokens::*;
\ No newline at end of file
+pub use withdraw_tokens::*;
+pub use init_host::*;
+pub use host_claim_all::*;
+pub use set_payout_destination::*;
+pub use crank_claim_rewards::*;
+pub use set_auto_compound::*;
+pub use migrate_admin_account::*;
+pub use migrate_nfnode_entry::*;
+pub use migrate_reward_entry::*;
+pub use migrate_host::*;
+pub use migrate_payout_config::*;
+pub use migrate_auto_compound::*;
+pub use set_mint_authority_grant::*;
+pub use set_nfnode_collection::*;
+pub use add_identity_source::*;
+pub use remove_identity_source::*;
+pub use init_asset_nfnode::*;
+pub use asset_owner_claim_rewards::*;
+pub use asset_deposit_tokens::*;
+pub use asset_withdraw_tokens::*;
+pub use asset_set_payout_destination::*;
+pub use asset_set_auto_compound::*;
+pub use asset_set_stake_commission::*;
+pub use asset_set_deposit_carry::*;
+pub use asset_request_withdrawal::*;
+pub use asset_complete_withdrawal::*;
+pub use asset_crank_claim_rewards::*;
+pub use bind_device::*;
+pub use submit_heartbeat::*;
+pub use assert_location::*;
+pub use set_h3_region_multiplier::*;
+pub use set_claim_period::*;
+pub use set_emergency_withdrawal_grace_period::*;
+pub use set_pause_flags::*;
+pub use freeze_nfnode::*;
+pub use appeal_nfnode_freeze::*;
+pub use blacklist_wallet::*;
+pub use remove_blacklisted_wallet::*;
+pub use appeal_blacklist::*;
+pub use init_reward_pool::*;
+pub use create_reward_stream::*;
+pub use drip::*;
+pub use close_reward_stream::*;
+pub use set_reserve_ratio::*;
+pub use claim_owed::*;
+pub use set_stake_commission::*;
+pub use stake::*;
+pub use unstake::*;
+pub use withdraw_unstaked::*;
+pub use claim_stake_rewards::*;
+pub use request_withdrawal::*;
+pub use complete_withdrawal::*;
+pub use set_deposit_carry::*;
@@ -1,96 +1,134 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::{ AssociatedToken },
-    token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
-    token_interface::{ Mint as Mint2022 },
+    token::{ Token, TokenAccount, Mint }, //Wayru Token
+};
+use crate::{
+    claim::{ require_claimable, settle_claim, Claim, ClaimAccounts },
+    collection::verify_nfnode_mint,
+    errors::RewardError,
+    location::apply_multiplier,
+    state::{
+        RewardEntry,
+        NfNodeEntry,
+        NfNodeIdentity,
+        AdminAccount,
+        MintAuthorityEntry,
+        ClaimRole,
+        NfNodeLocation,
+        RewardPool,
+    },
 };
-use crate::{ errors::RewardError, state::{ RewardEntry, NfNodeEntry, AdminAccount } };
 pub fn others_claim_rewards(
     ctx: Context<OthersClaimRewards>,
     reward_amount: u64,
     nonce: u64
 ) -> Result<()> {
-    // Validate that the reward amount is greater than zero
-    require!(reward_amount > 0, RewardError::InvalidRewardAmount);
-
-    let reward_entry = &mut ctx.accounts.reward_entry;
-    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
-    let amount = 5000000000;
-    require!(nfnode_entry.deposit_amount == amount, RewardError::DepositRequired);
-
-    let admin_account = &ctx.accounts.admin_account;
-    require!(!admin_account.paused, RewardError::ProgramPaused);
-    require!(
-        nonce > reward_entry.last_claimed_nonce ||
-            (reward_entry.last_claimed_nonce == 0 && nonce == 1) || // initialization
-            (reward_entry.last_claimed_nonce == u64::MAX && nonce == 1), // overflow unprobably
-        RewardError::NonceAlreadyClaimed
-    );
-    require!(
-        ctx.accounts.user_admin.key() == admin_account.admin_pubkey,
-        RewardError::UnauthorizedAdmin
-    );
-    let user_admin_account_info = ctx.accounts.user_admin.to_account_info();
-    let is_partially_signed_by_admin = user_admin_account_info.is_signer;
-    require!(is_partially_signed_by_admin, RewardError::MissingAdminSignature);
-    let current_timestamp = Clock::get()?.unix_timestamp;
-    msg!("Current timestamp: {}", current_timestamp);
-    let last_claim_day_reward_entry = reward_entry.last_claimed_timestamp
-        .checked_div(86400)
-        .ok_or(RewardError::ArithmeticOverflow)?;
-    let host_last_claim_day_nfnode_entry = nfnode_entry.host_last_claimed_timestamp
-        .checked_div(86400)
-        .ok_or(RewardError::ArithmeticOverflow)?;
-    let manufacturer_last_claim_day_nfnode_entry = nfnode_entry.manufacturer_last_claimed_timestamp
-        .checked_div(86400)
-        .ok_or(RewardError::ArithmeticOverflow)?;
-    let current_day = current_timestamp.checked_div(86400).ok_or(RewardError::ArithmeticOverflow)?;
-    require!(
-        (current_day > last_claim_day_reward_entry &&
-            current_day > host_last_claim_day_nfnode_entry) ||
-            (current_day > last_claim_day_reward_entry &&
-                current_day > manufacturer_last_claim_day_nfnode_entry),
-        RewardError::ClaimAlreadyMadeToday
-    );
-    //validate if nft has valid mint authority
-    let metadata_account_info = &ctx.accounts.nft_mint_address.to_account_info();
-    let metadata_account_data = metadata_account_info.try_borrow_data()?;
-    let mint = Mint2022::try_deserialize(&mut &metadata_account_data[..])?;
-    let mint_authority = mint.mint_authority.unwrap();
+    let role = ctx.accounts.nfnode_entry.others_claim_role(&ctx.accounts.user.key())?;
+    role_claim_rewards(ctx, role, reward_amount, nonce)
+}
+pub fn host_claim_rewards(
+    ctx: Context<OthersClaimRewards>,
+    reward_amount: u64,
+    nonce: u64
+) -> Result<()> {
+    role_claim_rewards(ctx, ClaimRole::Host, reward_amount, nonce)
+}
+pub fn manufacturer_claim_rewards(
+    ctx: Context<OthersClaimRewards>,
+    reward_amount: u64,
+    nonce: u64
+) -> Result<()> {
+    role_claim_rewards(ctx, ClaimRole::Manufacturer, reward_amount, nonce)
+}
+// host and manufacturer claims, each role has its own cooldown and deposit rule
+fn role_claim_rewards(
+    ctx: Context<OthersClaimRewards>,
+    role: ClaimRole,
+    reward_amount: u64,
+    nonce: u64
+) -> Result<()> {
     require!(
-        admin_account.mint_authorities.contains(&mint_authority),
-        RewardError::UnauthorizedMintAuthority
+        ctx.accounts.nfnode_entry.role_beneficiary(&role) == Some(ctx.accounts.user.key()),
+        RewardError::UnauthorizedUser
     );
+    // Validate that the reward amount is greater than zero
+    require!(reward_amount > 0, RewardError::InvalidRewardAmount);
+    // coverage multiplier of the region the nfnode is located in
+    let reward_amount = apply_multiplier(
+        reward_amount,
+        NfNodeLocation::reward_multiplier_bps(
+            &ctx.accounts.nfnode_location,
+            ctx.accounts.h3_region.as_ref().map(|h3_region| h3_region.as_ref())
+        )?
+    )?;
 
-    reward_entry.last_claimed_nonce = nonce;
-    reward_entry.last_claimed_timestamp = current_timestamp;
-    //verify if host or manufacturer
-    if ctx.accounts.user.key() == nfnode_entry.host {
-        nfnode_entry.host_last_claimed_timestamp = current_timestamp;
-    } else if ctx.accounts.user.key() == nfnode_entry.manufacturer {
-        nfnode_entry.manufacturer_last_claimed_timestamp = current_timestamp;
-    } else {
-        return Err(RewardError::UnauthorizedUser.into());
+    let claim = Claim {
+        role,
+        beneficiary: ctx.accounts.user.key(),
+        reward_amount,
+        crank_fee: 0,
+        auto_compound: None,
+        deposit_requirement: 5000000000,
+        now: Clock::get()?.unix_timestamp,
+    };
+    // the reward entry is keyed by wallet and node, so a wallet holding both roles signs one
+    // nonce sequence across them
+    require_claimable(
+        &ctx.accounts.admin_account,
+        &ctx.accounts.user_admin.to_account_info(),
+        &ctx.accounts.reward_entry,
+        &ctx.accounts.nfnode_entry,
+        nonce,
+        &claim,
+        &ctx.accounts.blacklist_entry
+    )?;
+    //validate if nft has valid mint authority, asset identities were validated at registration
+    if ctx.accounts.nfnode_entry.identity == NfNodeIdentity::Token2022 {
+        let metadata_account_info = &ctx.accounts.nft_mint_address.to_account_info();
+        require_keys_eq!(
+            *metadata_account_info.owner,
+            anchor_spl::token_2022::ID,
+            RewardError::InvalidNftMint
+        );
+        verify_nfnode_mint(
+            metadata_account_info,
+            &ctx.accounts.admin_account.collection,
+            &ctx.accounts.nfnode_entry.nfnode_type,
+            ctx.accounts.mint_authority_entry.as_deref()
+        )?;
     }
-    nfnode_entry.total_rewards_claimed += reward_amount;
 
     let authority_bump = ctx.bumps.token_storage_authority;
     let authority_seeds = &[&b"token_storage"[..], &[authority_bump]];
     let signer_seeds = &[&authority_seeds[..]];
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.token_storage_account.to_account_info(),
-                to: ctx.accounts.user_token_account.to_account_info(),
-                authority: ctx.accounts.token_storage_authority.to_account_info(),
-            },
-            signer_seeds
-        ),
-        reward_amount
-    )?;
-
-    Ok(())
+    settle_claim(
+        &mut ctx.accounts.reward_pool,
+        &mut ctx.accounts.reward_entry,
+        &mut ctx.accounts.nfnode_entry,
+        nonce,
+        claim,
+        ClaimAccounts {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            token_storage_account: &ctx.accounts.token_storage_account,
+            token_storage_authority: ctx.accounts.token_storage_authority.to_account_info(),
+            treasury_token_account: ctx.accounts.treasury_token_account
+                .as_ref()
+                .map(|treasury_token_account| treasury_token_account.to_account_info()),
+            payout_config: ctx.accounts.payout_config.to_account_info(),
+            beneficiary_token_account: ctx.accounts.user_token_account.to_account_info(),
+            recipient_token_account: ctx.accounts.recipient_token_account.as_deref(),
+            recipient_blacklist_entry: ctx.accounts.recipient_blacklist_entry
+                .as_ref()
+                .map(|recipient_blacklist_entry| recipient_blacklist_entry.to_account_info()),
+            nfnode_stake: None,
+            stake_vault_account: None,
+            deposit_storage_account: None,
+            cranker_token_account: None,
+        },
+        signer_seeds
+    )
 }
 #[derive(Accounts)]
 pub struct OthersClaimRewards<'info> {
@@ -99,12 +137,12 @@ pub struct OthersClaimRewards<'info> {
     pub user_admin: Signer<'info>,
     #[account(mut)]
     pub user: Signer<'info>,
-    /// CHECK:
-    pub nft_mint_address: InterfaceAccount<'info, Mint2022>,
+    /// CHECK: token-2022 nft mint or asset id of the nfnode, checked against the nfnode identity
+    pub nft_mint_address: UncheckedAccount<'info>,
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + std::mem::size_of::<RewardEntry>(),
+        space = 8 + RewardEntry::INIT_SPACE,
         seeds = [b"reward_entry", user.key().as_ref(), nft_mint_address.key().as_ref()],
         bump
     )]
@@ -115,6 +153,7 @@ pub struct OthersClaimRewards<'info> {
         bump
     )]
     pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(mut)]
     pub token_mint: Account<'info, Mint>,
     /// CHECK:
     #[account(mut, seeds = [b"token_storage"], bump)]
@@ -125,6 +164,10 @@ pub struct OthersClaimRewards<'info> {
         associated_token::authority = token_storage_authority,
     )]
     pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(mut, token::mint = token_mint)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
     #[account(
         init_if_needed,
         payer = user,
@@ -132,8 +175,25 @@ pub struct OthersClaimRewards<'info> {
         associated_token::authority = user
     )]
     pub user_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: payout routing set by set_payout_destination, may be uninitialized
+    #[account(seeds = [b"payout_config", nft_mint_address.key().as_ref()], bump)]
+    pub payout_config: UncheckedAccount<'info>,
+    #[account(mut, token::mint = token_mint)]
+    pub recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: blacklist entry of the wallet owning recipient_token_account, checked by the handler
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
+    /// CHECK: blacklist entry of the beneficiary, only initialized for blacklisted wallets
+    #[account(seeds = [b"blacklist", user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    /// CHECK: location asserted by assert_location, may be uninitialized
+    #[account(seeds = [b"nfnode_location", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_location: UncheckedAccount<'info>,
+    /// CHECK: region multiplier of the nfnode location, required once a location is asserted
+    pub h3_region: Option<UncheckedAccount<'info>>,
     #[account(seeds = [b"admin_account"], bump)]
     pub admin_account: Account<'info, AdminAccount>,
+    // authorization record of the nft mint authority, required for token-2022 nfnodes
+    pub mint_authority_entry: Option<Account<'info, MintAuthorityEntry>>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
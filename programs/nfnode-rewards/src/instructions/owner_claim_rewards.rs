@@ -1,10 +1,25 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::{ AssociatedToken },
-    token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
+    token::{ Token, TokenAccount, Mint }, //Wayru Token
     token_interface::{ Mint as Mint2022, TokenAccount as SplToken2022Account, TokenInterface },
 };
-use crate::{ errors::RewardError, state::{ RewardEntry, NfNodeEntry, AdminAccount, NfNodeType } };
+use crate::{
+    claim::{ require_claimable, settle_claim, Claim, ClaimAccounts },
+    collection::verify_nfnode_mint,
+    errors::RewardError,
+    location::apply_multiplier,
+    state::{
+        RewardEntry,
+        NfNodeEntry,
+        AdminAccount,
+        MintAuthorityEntry,
+        ClaimRole,
+        AutoCompound,
+        NfNodeLocation,
+        RewardPool,
+    },
+};
 pub fn owner_claim_rewards(
     ctx: Context<OwnerClaimRewards>,
     reward_amount: u64,
@@ -12,42 +27,43 @@ pub fn owner_claim_rewards(
 ) -> Result<()> {
     // Validate that the reward amount is greater than zero
     require!(reward_amount > 0, RewardError::InvalidRewardAmount);
-
-    let reward_entry = &mut ctx.accounts.reward_entry;
-    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    // coverage multiplier of the region the nfnode is located in
+    let reward_amount = apply_multiplier(
+        reward_amount,
+        NfNodeLocation::reward_multiplier_bps(
+            &ctx.accounts.nfnode_location,
+            ctx.accounts.h3_region.as_ref().map(|h3_region| h3_region.as_ref())
+        )?
+    )?;
     let amount = 5000000000;
-    //validate if type is not DON to validate the amount deposited
-    if nfnode_entry.nfnode_type !=NfNodeType::DON {
-    require!(nfnode_entry.deposit_amount == amount, RewardError::DepositRequired);}
-    let admin_account = &ctx.accounts.admin_account;
-    require!(!admin_account.paused, RewardError::ProgramPaused);
-    require!(
-        nonce > reward_entry.last_claimed_nonce ||
-            (reward_entry.last_claimed_nonce == 0 && nonce == 1) || // initialization
-            (reward_entry.last_claimed_nonce == u64::MAX && nonce == 1), // overflow unprobably
-        RewardError::NonceAlreadyClaimed
-    );
-    require!(
-        ctx.accounts.user_admin.key() == admin_account.admin_pubkey,
-        RewardError::UnauthorizedAdmin
-    );
-    let user_admin_account_info = ctx.accounts.user_admin.to_account_info();
-    let is_partially_signed_by_admin = user_admin_account_info.is_signer;
-    require!(is_partially_signed_by_admin, RewardError::MissingAdminSignature);
-    let current_timestamp = Clock::get()?.unix_timestamp;
-    msg!("Current timestamp: {}", current_timestamp);
-    let last_claim_day_reward_entry = reward_entry.last_claimed_timestamp
-        .checked_div(86400)
-        .ok_or(RewardError::ArithmeticOverflow)?;
-    let owner_last_claim_day_nfnode_entry = nfnode_entry.owner_last_claimed_timestamp
-        .checked_div(86400)
-        .ok_or(RewardError::ArithmeticOverflow)?;
-    let current_day = current_timestamp.checked_div(86400).ok_or(RewardError::ArithmeticOverflow)?;
-    require!(
-        current_day > last_claim_day_reward_entry &&
-            current_day > owner_last_claim_day_nfnode_entry,
-        RewardError::ClaimAlreadyMadeToday
-    );
+    // longer deposit lockups boost owner rewards
+    let reward_amount = apply_multiplier(
+        reward_amount,
+        ctx.accounts.nfnode_entry.lockup_multiplier_bps(amount)
+    )?;
+
+    // auto-compounding nodes fill the deposit from claims during the coverage period
+    let claim = Claim {
+        role: ClaimRole::Owner,
+        beneficiary: ctx.accounts.user.key(),
+        reward_amount,
+        crank_fee: 0,
+        auto_compound: AutoCompound::load_for(
+            &ctx.accounts.auto_compound.to_account_info(),
+            &ctx.accounts.user.key()
+        )?,
+        deposit_requirement: amount,
+        now: Clock::get()?.unix_timestamp,
+    };
+    require_claimable(
+        &ctx.accounts.admin_account,
+        &ctx.accounts.user_admin.to_account_info(),
+        &ctx.accounts.reward_entry,
+        &ctx.accounts.nfnode_entry,
+        nonce,
+        &claim,
+        &ctx.accounts.blacklist_entry
+    )?;
 
     let user_nft_token_account_info = &ctx.accounts.user_nft_token_account;
 
@@ -79,38 +95,48 @@ pub fn owner_claim_rewards(
     if user_nft_token_account.mint != ctx.accounts.nft_mint_address.key() {
         return err!(RewardError::InvalidNftMint);
     }
-    //validate if nft has valid mint authority
-    let metadata_account_info = &ctx.accounts.nft_mint_address.to_account_info();
-    let metadata_account_data = metadata_account_info.try_borrow_data()?;
-    let mint = Mint2022::try_deserialize(&mut &metadata_account_data[..])?;
-    let mint_authority = mint.mint_authority.unwrap();
-    require!(
-        admin_account.mint_authorities.contains(&mint_authority),
-        RewardError::UnauthorizedMintAuthority
-    );
-
-    reward_entry.last_claimed_nonce = nonce;
-    reward_entry.last_claimed_timestamp = current_timestamp;
-    nfnode_entry.owner_last_claimed_timestamp = current_timestamp;
-    nfnode_entry.total_rewards_claimed += reward_amount;
+    //validate if nft has valid mint authority or is a member of the nfnode collection
+    verify_nfnode_mint(
+        &ctx.accounts.nft_mint_address.to_account_info(),
+        &ctx.accounts.admin_account.collection,
+        &ctx.accounts.nfnode_entry.nfnode_type,
+        ctx.accounts.mint_authority_entry.as_deref()
+    )?;
 
     let authority_bump = ctx.bumps.token_storage_authority;
     let authority_seeds = &[&b"token_storage"[..], &[authority_bump]];
     let signer_seeds = &[&authority_seeds[..]];
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.token_storage_account.to_account_info(),
-                to: ctx.accounts.user_token_account.to_account_info(),
-                authority: ctx.accounts.token_storage_authority.to_account_info(),
-            },
-            signer_seeds
-        ),
-        reward_amount
-    )?;
-
-    Ok(())
+    settle_claim(
+        &mut ctx.accounts.reward_pool,
+        &mut ctx.accounts.reward_entry,
+        &mut ctx.accounts.nfnode_entry,
+        nonce,
+        claim,
+        ClaimAccounts {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            token_storage_account: &ctx.accounts.token_storage_account,
+            token_storage_authority: ctx.accounts.token_storage_authority.to_account_info(),
+            treasury_token_account: ctx.accounts.treasury_token_account
+                .as_ref()
+                .map(|treasury_token_account| treasury_token_account.to_account_info()),
+            payout_config: ctx.accounts.payout_config.to_account_info(),
+            beneficiary_token_account: ctx.accounts.user_token_account.to_account_info(),
+            recipient_token_account: ctx.accounts.recipient_token_account.as_deref(),
+            recipient_blacklist_entry: ctx.accounts.recipient_blacklist_entry
+                .as_ref()
+                .map(|recipient_blacklist_entry| recipient_blacklist_entry.to_account_info()),
+            nfnode_stake: Some(ctx.accounts.nfnode_stake.to_account_info()),
+            stake_vault_account: ctx.accounts.stake_vault_account
+                .as_ref()
+                .map(|stake_vault_account| stake_vault_account.to_account_info()),
+            deposit_storage_account: ctx.accounts.deposit_storage_account
+                .as_ref()
+                .map(|deposit_storage_account| deposit_storage_account.to_account_info()),
+            cranker_token_account: None,
+        },
+        signer_seeds
+    )
 }
 #[derive(Accounts)]
 pub struct OwnerClaimRewards<'info> {
@@ -124,7 +150,7 @@ pub struct OwnerClaimRewards<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + std::mem::size_of::<RewardEntry>(),
+        space = 8 + RewardEntry::INIT_SPACE,
         seeds = [b"reward_entry", user.key().as_ref(), nft_mint_address.key().as_ref()],
         bump
     )]
@@ -135,6 +161,7 @@ pub struct OwnerClaimRewards<'info> {
         bump
     )]
     pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(mut)]
     pub token_mint: Account<'info, Mint>,
     /// CHECK:
     #[account(mut, seeds = [b"token_storage"], bump)]
@@ -145,6 +172,22 @@ pub struct OwnerClaimRewards<'info> {
         associated_token::authority = token_storage_authority,
     )]
     pub token_storage_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(mut, token::mint = token_mint)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: delegated stake on the nfnode, may be uninitialized
+    #[account(mut, seeds = [b"nfnode_stake", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_stake: UncheckedAccount<'info>,
+    /// CHECK: delegated stake vault authority
+    #[account(seeds = [b"stake_vault", nft_mint_address.key().as_ref()], bump)]
+    pub stake_vault_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = stake_vault_authority,
+    )]
+    pub stake_vault_account: Option<Box<Account<'info, TokenAccount>>>,
     #[account(
         init_if_needed,
         payer = user,
@@ -152,11 +195,40 @@ pub struct OwnerClaimRewards<'info> {
         associated_token::authority = user
     )]
     pub user_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: payout routing set by set_payout_destination, may be uninitialized
+    #[account(seeds = [b"payout_config", nft_mint_address.key().as_ref()], bump)]
+    pub payout_config: UncheckedAccount<'info>,
+    #[account(mut, token::mint = token_mint)]
+    pub recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// CHECK: blacklist entry of the wallet owning recipient_token_account, checked by the handler
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
+    /// CHECK: auto-compound setting set by set_auto_compound, may be uninitialized
+    #[account(seeds = [b"auto_compound", nft_mint_address.key().as_ref()], bump)]
+    pub auto_compound: UncheckedAccount<'info>,
+    /// CHECK: nfnode deposit escrow authority
+    #[account(seeds = [b"token_storage", nft_mint_address.key().as_ref()], bump)]
+    pub deposit_storage_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = deposit_storage_authority,
+    )]
+    pub deposit_storage_account: Option<Box<Account<'info, TokenAccount>>>,
     /// CHECK: used to check nft ownership
     pub user_nft_token_account: AccountInfo<'info>,
     // pub user_nft_token_account: InterfaceAccount<'info, SplToken2022Account>,
+    /// CHECK: blacklist entry of the beneficiary, only initialized for blacklisted wallets
+    #[account(seeds = [b"blacklist", user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    /// CHECK: location asserted by assert_location, may be uninitialized
+    #[account(seeds = [b"nfnode_location", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_location: UncheckedAccount<'info>,
+    /// CHECK: region multiplier of the nfnode location, required once a location is asserted
+    pub h3_region: Option<UncheckedAccount<'info>>,
     #[account(seeds = [b"admin_account"], bump)]
     pub admin_account: Account<'info, AdminAccount>,
+    // authorization record of the nft mint authority, not needed for members of the nfnode collection
+    pub mint_authority_entry: Option<Account<'info, MintAuthorityEntry>>,
     pub token_program_2022: Interface<'info, TokenInterface>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
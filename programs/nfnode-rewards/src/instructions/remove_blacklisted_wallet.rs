@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ AdminAccount, BlacklistEntry } };
+pub fn remove_blacklisted_wallet(ctx: Context<RemoveBlacklistedWallet>, wallet: Pubkey) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    require!(ctx.accounts.blacklist_entry.wallet == wallet, RewardError::InvalidPubkey);
+    Ok(())
+}
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct RemoveBlacklistedWallet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"blacklist", wallet.as_ref()],
+        bump
+    )]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ AdminAccount, IdentitySource } };
+pub fn remove_identity_source(ctx: Context<RemoveIdentitySource>, source: Pubkey) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    require!(ctx.accounts.identity_source.source == source, RewardError::InvalidIdentitySource);
+    Ok(())
+}
+#[derive(Accounts)]
+#[instruction(source: Pubkey)]
+pub struct RemoveIdentitySource<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"identity_source", source.as_ref()],
+        bump
+    )]
+    pub identity_source: Account<'info, IdentitySource>,
+}
@@ -1,20 +1,27 @@
 use anchor_lang::prelude::*;
 
-use crate::{ errors::RewardError, state::AdminAccount };
+use crate::{ errors::RewardError, state::{ AdminAccount, MintAuthorityEntry } };
 pub fn remove_mint_authority(ctx: Context<RemoveMintAuthority>, mint_authority: Pubkey) -> Result<()> {
-    let admin_account = &mut ctx.accounts.admin_account;
+    let admin_account = &ctx.accounts.admin_account;
     require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
-    if let Some(index) = admin_account.mint_authorities.iter().position(|&x| x == mint_authority) {
-        admin_account.mint_authorities.remove(index);
-        Ok(())
-    } else {
-        Err(RewardError::MintAuthorityNotFound.into())
-    }
+    require!(
+        ctx.accounts.mint_authority_entry.authority == mint_authority,
+        RewardError::MintAuthorityNotFound
+    );
+    Ok(())
 }
 #[derive(Accounts)]
+#[instruction(mint_authority: Pubkey)]
 pub struct RemoveMintAuthority<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(mut, seeds = [b"admin_account"], bump)]
+    #[account(seeds = [b"admin_account"], bump)]
     pub admin_account: Account<'info, AdminAccount>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"mint_authority", mint_authority.as_ref()],
+        bump
+    )]
+    pub mint_authority_entry: Account<'info, MintAuthorityEntry>,
 }
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount as SplToken2022Account;
+
+use crate::state::{ AdminAccount, NfNodeEntry };
+// starts unbonding the whole deposit, claims on the nfnode stop until it is cancelled. Once the
+// emergency withdrawal grace period has passed neither the lockup nor a freeze holds the deposit
+pub fn request_withdrawal(ctx: Context<RequestWithdrawal>) -> Result<()> {
+    let holds_nft = ctx.accounts.holds_nft();
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let emergency_withdrawal_open = ctx.accounts.admin_account.emergency_withdrawal_open(
+        current_timestamp
+    );
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    nfnode_entry.require_deposit_owner(&ctx.accounts.user.key(), holds_nft)?;
+    nfnode_entry.request_unbonding(emergency_withdrawal_open, current_timestamp)
+}
+// rebonds the deposit, claims resume and the unbonding period starts over on the next request
+pub fn cancel_withdrawal(ctx: Context<RequestWithdrawal>) -> Result<()> {
+    let holds_nft = ctx.accounts.holds_nft();
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    nfnode_entry.require_deposit_owner(&ctx.accounts.user.key(), holds_nft)?;
+    nfnode_entry.cancel_unbonding()
+}
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    pub user: Signer<'info>,
+    /// CHECK: token-2022 nft mint of the nfnode
+    pub nft_mint_address: UncheckedAccount<'info>,
+    // left out by a depositor who no longer holds the nft
+    #[account(token::mint = nft_mint_address, token::authority = user)]
+    pub user_nft_token_account: Option<InterfaceAccount<'info, SplToken2022Account>>,
+    #[account(mut, seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+}
+impl<'info> RequestWithdrawal<'info> {
+    fn holds_nft(&self) -> bool {
+        self.user_nft_token_account
+            .as_ref()
+            .is_some_and(|user_nft_token_account| user_nft_token_account.amount > 0)
+    }
+}
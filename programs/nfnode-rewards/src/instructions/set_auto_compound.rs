@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{ Mint as Mint2022, TokenAccount as SplToken2022Account, TokenInterface },
+};
+use crate::{ errors::RewardError, state::AutoCompound };
+pub fn set_auto_compound(
+    ctx: Context<SetAutoCompound>,
+    compound_bps: u16,
+    stake_beyond_requirement: bool
+) -> Result<()> {
+    require!(compound_bps <= 10000, RewardError::InvalidCompoundShare);
+    let user_nft_token_account_info = &ctx.accounts.user_nft_token_account;
+
+    if user_nft_token_account_info.owner != &ctx.accounts.token_program_2022.key() {
+        return err!(RewardError::InvalidNftMint);
+    }
+    // Manually derive the associated token account PDA
+    let (derived_ata, _bump_seed) = Pubkey::find_program_address(
+        &[
+            &ctx.accounts.user.key().to_bytes(),
+            &ctx.accounts.token_program_2022.key().to_bytes(),
+            &ctx.accounts.nft_mint_address.key().to_bytes(),
+        ],
+        &ctx.accounts.associated_token_program.key()
+    );
+
+    // Validate the ownership of the user_nft_token_account
+    require!(derived_ata == *user_nft_token_account_info.key, RewardError::InvalidNftTokenAccount);
+    let user_nft_token_account_data = user_nft_token_account_info.try_borrow_data()?;
+    let user_nft_token_account = SplToken2022Account::try_deserialize(
+        &mut &user_nft_token_account_data[..]
+    )?;
+
+    if user_nft_token_account.amount == 0 {
+        return err!(RewardError::InsufficientNftBalance);
+    }
+
+    if user_nft_token_account.mint != ctx.accounts.nft_mint_address.key() {
+        return err!(RewardError::InvalidNftMint);
+    }
+
+    // compound_bps = 0 disables auto-compounding
+    let auto_compound = &mut ctx.accounts.auto_compound;
+    auto_compound.version = AutoCompound::VERSION;
+    auto_compound.owner = ctx.accounts.user.key();
+    auto_compound.compound_bps = compound_bps;
+    auto_compound.stake_beyond_requirement = stake_beyond_requirement;
+    // the coverage period is not restarted by changing the setting or the nft changing hands
+    if compound_bps > 0 && auto_compound.enabled_at == 0 {
+        auto_compound.enabled_at = Clock::get()?.unix_timestamp;
+    }
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct SetAutoCompound<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    ///CHECK: only read account
+    pub nft_mint_address: InterfaceAccount<'info, Mint2022>,
+    /// CHECK: used to check nft ownership
+    pub user_nft_token_account: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + AutoCompound::INIT_SPACE,
+        seeds = [b"auto_compound", nft_mint_address.key().as_ref()],
+        bump
+    )]
+    pub auto_compound: Account<'info, AutoCompound>,
+    pub token_program_2022: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ AdminAccount, ClaimPeriodMode } };
+pub fn set_claim_period(
+    ctx: Context<SetClaimPeriod>,
+    mode: ClaimPeriodMode,
+    period_seconds: i64,
+    offset_seconds: i64
+) -> Result<()> {
+    let admin_account = &mut ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    require!(period_seconds > 0, RewardError::InvalidClaimPeriod);
+    // the offset only shifts calendar aligned periods, e.g. 3600 starts daily periods at 01:00 UTC
+    require!(
+        (0..period_seconds).contains(&offset_seconds) &&
+            (mode == ClaimPeriodMode::CalendarAligned || offset_seconds == 0),
+        RewardError::InvalidClaimPeriod
+    );
+    admin_account.claim_period_mode = mode;
+    admin_account.claim_period_seconds = period_seconds;
+    admin_account.claim_period_offset = offset_seconds;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct SetClaimPeriod<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut, seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+}
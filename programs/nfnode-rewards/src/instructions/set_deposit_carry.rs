@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount as SplToken2022Account;
+
+use crate::{ errors::RewardError, state::{ NfNodeEntry, NfNodeIdentity } };
+// chooses whether a sale of the nft hands the deposit to the buyer, whoever sets it owns the deposit
+pub fn set_deposit_carry(ctx: Context<SetDepositCarry>, carries_with_nft: bool) -> Result<()> {
+    let holds_nft = ctx.accounts.user_nft_token_account
+        .as_ref()
+        .is_some_and(|user_nft_token_account| user_nft_token_account.amount > 0);
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    nfnode_entry.require_deposit_owner(&ctx.accounts.user.key(), holds_nft)?;
+    // asset nodes prove who holds the asset through asset_set_deposit_carry
+    require!(
+        !carries_with_nft || nfnode_entry.identity == NfNodeIdentity::Token2022,
+        RewardError::InvalidIdentitySource
+    );
+    nfnode_entry.set_deposit_carry(&ctx.accounts.user.key(), carries_with_nft)
+}
+#[derive(Accounts)]
+pub struct SetDepositCarry<'info> {
+    pub user: Signer<'info>,
+    /// CHECK: token-2022 nft mint of the nfnode
+    pub nft_mint_address: UncheckedAccount<'info>,
+    // left out by a depositor who no longer holds the nft
+    #[account(token::mint = nft_mint_address, token::authority = user)]
+    pub user_nft_token_account: Option<InterfaceAccount<'info, SplToken2022Account>>,
+    #[account(mut, seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+}
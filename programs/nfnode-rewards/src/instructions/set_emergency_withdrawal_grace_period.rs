@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::RewardError,
+    instructions::UpdateAdmin,
+    state::MAX_EMERGENCY_WITHDRAWAL_GRACE_PERIOD,
+};
+pub fn set_emergency_withdrawal_grace_period(
+    ctx: Context<UpdateAdmin>,
+    grace_period: i64
+) -> Result<()> {
+    let admin_account = &mut ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    // capped so the admin cannot keep deposits locked indefinitely
+    require!(
+        grace_period > 0 && grace_period <= MAX_EMERGENCY_WITHDRAWAL_GRACE_PERIOD,
+        RewardError::InvalidGracePeriod
+    );
+    admin_account.emergency_withdrawal_grace_period = grace_period;
+    Ok(())
+}
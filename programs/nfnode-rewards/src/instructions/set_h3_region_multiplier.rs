@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::RewardError,
+    location::{ h3_resolution, validate_h3_cell },
+    state::{ AdminAccount, H3Region, H3_REGION_RESOLUTION, MAX_REGION_MULTIPLIER_BPS },
+};
+pub fn set_h3_region_multiplier(
+    ctx: Context<SetH3RegionMultiplier>,
+    region_cell: u64,
+    multiplier_bps: u16
+) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    validate_h3_cell(region_cell, H3_REGION_RESOLUTION)?;
+    require!(h3_resolution(region_cell) == H3_REGION_RESOLUTION, RewardError::InvalidH3Cell);
+    require!(multiplier_bps <= MAX_REGION_MULTIPLIER_BPS, RewardError::InvalidRegionMultiplier);
+    let h3_region = &mut ctx.accounts.h3_region;
+    h3_region.version = H3Region::VERSION;
+    h3_region.region_cell = region_cell;
+    h3_region.multiplier_bps = multiplier_bps;
+    Ok(())
+}
+#[derive(Accounts)]
+#[instruction(region_cell: u64)]
+pub struct SetH3RegionMultiplier<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + H3Region::INIT_SPACE,
+        seeds = [b"h3_region", region_cell.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub h3_region: Account<'info, H3Region>,
+    pub system_program: Program<'info, System>,
+}
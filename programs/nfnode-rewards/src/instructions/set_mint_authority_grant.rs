@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ AdminAccount, MintAuthorityEntry, NfNodeType } };
+pub fn set_mint_authority_grant(
+    ctx: Context<SetMintAuthorityGrant>,
+    mint_authority: Pubkey,
+    registration_opens_at: i64,
+    registration_closes_at: i64,
+    allowed_nfnode_types: Vec<NfNodeType>
+) -> Result<()> {
+    let admin_account = &ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    let mint_authority_entry = &mut ctx.accounts.mint_authority_entry;
+    require!(mint_authority_entry.authority == mint_authority, RewardError::MintAuthorityNotFound);
+    mint_authority_entry.set_grant(registration_opens_at, registration_closes_at, &allowed_nfnode_types)?;
+    Ok(())
+}
+#[derive(Accounts)]
+#[instruction(mint_authority: Pubkey)]
+pub struct SetMintAuthorityGrant<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(
+        mut,
+        seeds = [b"mint_authority", mint_authority.as_ref()],
+        bump
+    )]
+    pub mint_authority_entry: Account<'info, MintAuthorityEntry>,
+}
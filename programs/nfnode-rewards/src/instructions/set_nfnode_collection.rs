@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{ collection::verify_collection_group, errors::RewardError, state::AdminAccount };
+pub fn set_nfnode_collection(ctx: Context<SetNfNodeCollection>) -> Result<()> {
+    let admin_account = &mut ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    // no collection mint disables the collection check in initialize_nfnode
+    admin_account.collection = match &ctx.accounts.collection_mint {
+        Some(collection_mint) => {
+            verify_collection_group(collection_mint)?;
+            collection_mint.key()
+        }
+        None => Pubkey::default(),
+    };
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct SetNfNodeCollection<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut, seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    /// CHECK: token-group mint of the collection, validated by the handler
+    pub collection_mint: Option<UncheckedAccount<'info>>,
+}
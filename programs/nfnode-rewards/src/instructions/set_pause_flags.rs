@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, instructions::UpdateAdmin, state::PAUSE_ALL };
+pub fn set_pause_flags(
+    ctx: Context<UpdateAdmin>,
+    pause_flags: u8,
+    pause_reason: u16,
+    pause_expires_at: i64
+) -> Result<()> {
+    let admin_account = &mut ctx.accounts.admin_account;
+    require!(ctx.accounts.user.key() == admin_account.admin_pubkey, RewardError::UnauthorizedAdmin);
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    require!(pause_flags & !PAUSE_ALL == 0, RewardError::InvalidPauseFlags);
+    // an expiry in the past would lift the pause right away
+    require!(
+        pause_expires_at == 0 || pause_expires_at > current_timestamp,
+        RewardError::InvalidPauseFlags
+    );
+    admin_account.set_pause(pause_flags, pause_reason, pause_expires_at, current_timestamp);
+    Ok(())
+}
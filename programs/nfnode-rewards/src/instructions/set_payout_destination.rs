@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{ Mint as Mint2022, TokenAccount as SplToken2022Account, TokenInterface },
+};
+use crate::{ errors::RewardError, state::{ ClaimRole, NfNodeEntry, PayoutConfig } };
+pub fn set_payout_destination(
+    ctx: Context<SetPayoutDestination>,
+    role: ClaimRole,
+    destination: Pubkey
+) -> Result<()> {
+    let nfnode_entry = &ctx.accounts.nfnode_entry;
+    let user = ctx.accounts.user.key();
+    match role {
+        ClaimRole::Owner => {
+            let user_nft_token_account_info = ctx.accounts.user_nft_token_account
+                .as_ref()
+                .ok_or(RewardError::InvalidNftTokenAccount)?;
+            if user_nft_token_account_info.owner != &ctx.accounts.token_program_2022.key() {
+                return err!(RewardError::InvalidNftMint);
+            }
+            // Manually derive the associated token account PDA
+            let (derived_ata, _bump_seed) = Pubkey::find_program_address(
+                &[
+                    &user.to_bytes(),
+                    &ctx.accounts.token_program_2022.key().to_bytes(),
+                    &ctx.accounts.nft_mint_address.key().to_bytes(),
+                ],
+                &ctx.accounts.associated_token_program.key()
+            );
+
+            // Validate the ownership of the user_nft_token_account
+            require!(
+                derived_ata == user_nft_token_account_info.key(),
+                RewardError::InvalidNftTokenAccount
+            );
+            let user_nft_token_account_data = user_nft_token_account_info.try_borrow_data()?;
+            let user_nft_token_account = SplToken2022Account::try_deserialize(
+                &mut &user_nft_token_account_data[..]
+            )?;
+
+            if user_nft_token_account.amount == 0 {
+                return err!(RewardError::InsufficientNftBalance);
+            }
+
+            if user_nft_token_account.mint != ctx.accounts.nft_mint_address.key() {
+                return err!(RewardError::InvalidNftMint);
+            }
+        }
+        ClaimRole::Host => {
+            require!(user == nfnode_entry.host, RewardError::UnauthorizedUser);
+        }
+        ClaimRole::Manufacturer => {
+            require!(user == nfnode_entry.manufacturer, RewardError::UnauthorizedUser);
+        }
+    }
+
+    // Pubkey::default() clears the route and pays the signer again
+    let payout_config = &mut ctx.accounts.payout_config;
+    payout_config.version = PayoutConfig::VERSION;
+    let route = payout_config.route_mut(&role);
+    route.beneficiary = user;
+    route.destination = destination;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct SetPayoutDestination<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    ///CHECK: only read account
+    pub nft_mint_address: InterfaceAccount<'info, Mint2022>,
+    /// CHECK: used to check nft ownership, required for the owner role
+    pub user_nft_token_account: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PayoutConfig::INIT_SPACE,
+        seeds = [b"payout_config", nft_mint_address.key().as_ref()],
+        bump
+    )]
+    pub payout_config: Box<Account<'info, PayoutConfig>>,
+    pub token_program_2022: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ AdminAccount, RewardPool, MAX_RESERVE_RATIO_BPS } };
+pub fn set_reserve_ratio(ctx: Context<SetReserveRatio>, reserve_ratio_bps: u16) -> Result<()> {
+    require!(
+        ctx.accounts.user.key() == ctx.accounts.admin_account.admin_pubkey,
+        RewardError::UnauthorizedAdmin
+    );
+    require!(reserve_ratio_bps <= MAX_RESERVE_RATIO_BPS, RewardError::InvalidReserveRatio);
+    ctx.accounts.reward_pool.reserve_ratio_bps = reserve_ratio_bps;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct SetReserveRatio<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+}
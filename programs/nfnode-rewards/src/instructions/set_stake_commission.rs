@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount as SplToken2022Account;
+
+use crate::{ errors::RewardError, state::{ NfNodeEntry, NfNodeStake } };
+// the owner's cut of the rewards delegators earn on the nfnode and the most stake it accepts.
+// Opens the nfnode to delegated stake, a commission increase only applies after STAKE_COMMISSION_DELAY
+pub fn set_stake_commission(
+    ctx: Context<SetStakeCommission>,
+    commission_bps: u16,
+    stake_cap: u64
+) -> Result<()> {
+    require!(ctx.accounts.user_nft_token_account.amount > 0, RewardError::InsufficientNftBalance);
+    require!(commission_bps <= 10000, RewardError::InvalidStakeCommission);
+    ctx.accounts.nfnode_stake.configure(
+        ctx.accounts.nft_mint_address.key(),
+        commission_bps,
+        stake_cap,
+        Clock::get()?.unix_timestamp
+    );
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct SetStakeCommission<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: token-2022 nft mint of the nfnode
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(token::mint = nft_mint_address, token::authority = user)]
+    pub user_nft_token_account: InterfaceAccount<'info, SplToken2022Account>,
+    #[account(seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + NfNodeStake::INIT_SPACE,
+        seeds = [b"nfnode_stake", nft_mint_address.key().as_ref()],
+        bump
+    )]
+    pub nfnode_stake: Account<'info, NfNodeStake>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Mint, Token, TokenAccount, Transfer },
+};
+
+use crate::{
+    errors::RewardError,
+    state::{
+        AdminAccount,
+        BlacklistEntry,
+        NfNodeEntry,
+        NfNodeStake,
+        StakePosition,
+        PAUSE_DEPOSITS,
+    },
+};
+// any wallet can stake on an nfnode opened by its owner, up to the owner's cap. New stake earns a
+// share of the owner rewards once it is MIN_STAKE_AGE old
+pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+    require!(amount > 0, RewardError::InvalidStakeAmount);
+    let admin_account = &ctx.accounts.admin_account;
+    require!(
+        !admin_account.is_paused(PAUSE_DEPOSITS, Clock::get()?.unix_timestamp),
+        RewardError::ProgramPaused
+    );
+    require!(admin_account.valid_mint == ctx.accounts.token_mint.key(), RewardError::InvalidMint);
+    require!(!ctx.accounts.nfnode_entry.frozen, RewardError::NfNodeFrozen);
+    BlacklistEntry::require_not_blacklisted(&ctx.accounts.blacklist_entry)?;
+    ctx.accounts.nfnode_stake.require_stake_capacity(amount)?;
+
+    token::transfer(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), Transfer {
+            from: ctx.accounts.delegator_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault_account.to_account_info(),
+            authority: ctx.accounts.delegator.to_account_info(),
+        }),
+        amount
+    )?;
+
+    let nfnode_stake = &mut ctx.accounts.nfnode_stake;
+    let stake_position = &mut ctx.accounts.stake_position;
+    if stake_position.version == 0 {
+        stake_position.version = StakePosition::VERSION;
+        stake_position.delegator = ctx.accounts.delegator.key();
+        stake_position.nfnode_mint = ctx.accounts.nft_mint_address.key();
+    }
+    // rewards accrued on the existing stake are settled before it grows
+    stake_position.settle(nfnode_stake)?;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    stake_position.activate(nfnode_stake, current_timestamp)?;
+    stake_position.warming_amount = stake_position.warming_amount
+        .checked_add(amount)
+        .ok_or(RewardError::ArithmeticOverflow)?;
+    stake_position.warming_since = current_timestamp;
+    nfnode_stake.total_warming = nfnode_stake.total_warming
+        .checked_add(amount)
+        .ok_or(RewardError::ArithmeticOverflow)?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    /// CHECK: token-2022 nft mint or asset id of the nfnode, only used to derive its accounts
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    // opened by the nfnode owner with set_stake_commission
+    #[account(mut, seeds = [b"nfnode_stake", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_stake: Box<Account<'info, NfNodeStake>>,
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = 8 + StakePosition::INIT_SPACE,
+        seeds = [b"stake_position", nft_mint_address.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+    pub token_mint: Box<Account<'info, Mint>>,
+    /// CHECK: delegated stake vault authority
+    #[account(seeds = [b"stake_vault", nft_mint_address.key().as_ref()], bump)]
+    pub stake_vault_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        associated_token::mint = token_mint,
+        associated_token::authority = stake_vault_authority
+    )]
+    pub stake_vault_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = delegator,
+    )]
+    pub delegator_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: blacklist entry of the delegator, only initialized for blacklisted wallets
+    #[account(seeds = [b"blacklist", delegator.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
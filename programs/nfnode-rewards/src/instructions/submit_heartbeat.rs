@@ -0,0 +1,36 @@
+use anchor_lang::{ prelude::*, solana_program::sysvar::instructions as instructions_sysvar };
+
+use crate::{
+    device::{ heartbeat_message, verify_device_signature, HEARTBEAT_MAX_CLOCK_DRIFT },
+    errors::RewardError,
+    state::NfNodeEntry,
+};
+pub fn submit_heartbeat(ctx: Context<SubmitHeartbeat>, timestamp: i64) -> Result<()> {
+    let nfnode_entry = &mut ctx.accounts.nfnode_entry;
+    require!(nfnode_entry.device_pubkey != Pubkey::default(), RewardError::DeviceNotBound);
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    // increasing timestamps keep signed heartbeats from being replayed
+    require!(
+        timestamp > nfnode_entry.last_heartbeat_timestamp &&
+            (timestamp - current_timestamp).abs() <= HEARTBEAT_MAX_CLOCK_DRIFT,
+        RewardError::InvalidHeartbeatTimestamp
+    );
+    verify_device_signature(
+        &ctx.accounts.instructions_sysvar,
+        &nfnode_entry.device_pubkey,
+        &heartbeat_message(&ctx.accounts.nft_mint_address.key(), timestamp)
+    )?;
+    nfnode_entry.last_heartbeat_timestamp = timestamp;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct SubmitHeartbeat<'info> {
+    pub payer: Signer<'info>,
+    /// CHECK: only used to derive the nfnode entry
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
+    /// CHECK: instructions sysvar holding the Ed25519 precompile instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
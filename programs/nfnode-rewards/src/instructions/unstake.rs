@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{ errors::RewardError, state::{ NfNodeStake, StakePosition } };
+// unstaked tokens stop earning right away and can be withdrawn once the cooldown is over, stake still
+// warming up is unstaked first
+pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    require!(amount > 0, RewardError::InvalidStakeAmount);
+    let nfnode_stake = &mut ctx.accounts.nfnode_stake;
+    let stake_position = &mut ctx.accounts.stake_position;
+    stake_position.settle(nfnode_stake)?;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    stake_position.activate(nfnode_stake, current_timestamp)?;
+    require!(
+        (stake_position.staked_amount as u128) + (stake_position.warming_amount as u128) >=
+            (amount as u128),
+        RewardError::InsufficientStake
+    );
+    let warming_amount = amount.min(stake_position.warming_amount);
+    stake_position.warming_amount -= warming_amount;
+    nfnode_stake.total_warming -= warming_amount;
+    stake_position.staked_amount -= amount - warming_amount;
+    nfnode_stake.total_staked -= amount - warming_amount;
+    stake_position.unstaking_amount = stake_position.unstaking_amount
+        .checked_add(amount)
+        .ok_or(RewardError::ArithmeticOverflow)?;
+    // a new unstake restarts the cooldown of everything still unstaking
+    stake_position.unstake_requested_at = current_timestamp;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    pub delegator: Signer<'info>,
+    /// CHECK: only used to derive the stake accounts
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"nfnode_stake", nft_mint_address.key().as_ref()], bump)]
+    pub nfnode_stake: Account<'info, NfNodeStake>,
+    #[account(
+        mut,
+        seeds = [b"stake_position", nft_mint_address.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+}
@@ -3,7 +3,7 @@ use anchor_spl::{
     associated_token::{ AssociatedToken },
     token_interface::{ Mint as Mint2022, TokenAccount as SplToken2022Account, TokenInterface },
 };
-use crate::{ errors::RewardError, state::{ NfNodeEntry, AdminAccount } };
+use crate::{ errors::RewardError, state::{ NfNodeEntry, AdminAccount, MintAuthorityEntry } };
 pub fn update_nfnode(ctx: Context<UpdateNfNode>, host_share: u64) -> Result<()> {
     let admin_account = &ctx.accounts.admin_account;
     require!(
@@ -59,9 +59,9 @@ pub fn update_nfnode(ctx: Context<UpdateNfNode>, host_share: u64) -> Result<()>
     let metadata_account_info = &ctx.accounts.nft_mint_address.to_account_info();
     let metadata_account_data = metadata_account_info.try_borrow_data()?;
     let mint = Mint2022::try_deserialize(&mut &metadata_account_data[..])?;
-    let mint_authority = mint.mint_authority.unwrap();
+    let mint_authority = mint.mint_authority.ok_or(RewardError::UnauthorizedMintAuthority)?;
     require!(
-        admin_account.mint_authorities.contains(&mint_authority),
+        ctx.accounts.mint_authority_entry.authority == mint_authority,
         RewardError::UnauthorizedMintAuthority
     );
     let nfnode_entry = &mut ctx.accounts.nfnode_entry;
@@ -85,6 +85,8 @@ pub struct UpdateNfNode<'info> {
     pub nfnode_entry: Account<'info, NfNodeEntry>,
     #[account(seeds = [b"admin_account"], bump)]
     pub admin_account: Account<'info, AdminAccount>,
+    // authorization record of the nft mint authority, checked against the mint by the handler
+    pub mint_authority_entry: Account<'info, MintAuthorityEntry>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program_2022: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
@@ -4,72 +4,58 @@ use anchor_spl::{
     token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
     token_interface::{ Mint as Mint2022, TokenAccount as SplToken2022Account, TokenInterface },
 };
-use crate::{ errors::RewardError, state::{ NfNodeEntry, AdminAccount } };
-pub fn withdraw_tokens(ctx: Context<WithdrawTokens>) -> Result<()> {
-    let amount = 5000000000;
+use crate::{ collection::verify_nfnode_mint, errors::RewardError, state::{ NfNodeEntry, AdminAccount, MintAuthorityEntry, PAUSE_WITHDRAWALS } };
+// anything above the required deposit can be withdrawn at any time, the required minimum is
+// released through request_withdrawal and complete_withdrawal. DON nodes have no required deposit
+pub fn withdraw_tokens(ctx: Context<WithdrawTokens>, amount: u64) -> Result<()> {
     let nfnode_entry = &ctx.accounts.nfnode_entry;
-    require!(nfnode_entry.deposit_amount == amount, RewardError::WithdrawAlreadyMade);
+    require!(nfnode_entry.deposit_amount > 0, RewardError::WithdrawAlreadyMade);
+    require!(
+        amount > 0 && amount <= nfnode_entry.deposit_amount,
+        RewardError::InvalidWithdrawAmount
+    );
+    require!(!nfnode_entry.unbonding(), RewardError::NfNodeUnbonding);
     // Validate that token_mint is a valid mint registered in admin account
     let admin_account = &ctx.accounts.admin_account;
-    require!(!admin_account.paused, RewardError::ProgramPaused);
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    // a pause past its grace period no longer locks deposits in escrow
+    require!(
+        !admin_account.is_paused(PAUSE_WITHDRAWALS, current_timestamp) ||
+            admin_account.emergency_withdrawal_open(current_timestamp),
+        RewardError::ProgramPaused
+    );
+    require!(
+        !nfnode_entry.frozen || admin_account.emergency_withdrawal_open(current_timestamp),
+        RewardError::NfNodeFrozen
+    );
     let valid_mint = admin_account.valid_mint;
     let token_mint = &ctx.accounts.token_mint;
     require!(valid_mint == token_mint.key(), RewardError::InvalidMint);
 
-    let user_nft_token_account_info = &ctx.accounts.user_nft_token_account;
-
-    if user_nft_token_account_info.owner != &ctx.accounts.token_program_2022.key() {
-        return err!(RewardError::InvalidNftMint);
-    }
-    // Fetch the mint account data
-    let nft_mint_account_info = &ctx.accounts.nft_mint_address.to_account_info();
-    let nft_mint_account_data = nft_mint_account_info.try_borrow_data()?;
-    let nft_mint_account = Mint2022::try_deserialize(&mut &nft_mint_account_data[..])?;
-    
-    //validate if nft has valid mint authority
-    let mint_authority = nft_mint_account.mint_authority.unwrap();
-    require!(
-        admin_account.mint_authorities.contains(&mint_authority),
-        RewardError::UnauthorizedMintAuthority
-    );
+    let nft_mint_account = &ctx.accounts.nft_mint_address;
+    //validate if nft has valid mint authority or is a member of the nfnode collection
+    verify_nfnode_mint(
+        &nft_mint_account.to_account_info(),
+        &admin_account.collection,
+        &nfnode_entry.nfnode_type,
+        ctx.accounts.mint_authority_entry.as_deref()
+    )?;
     // Validate that the total supply is 1
     require!(nft_mint_account.supply == 1, RewardError::InvalidNftSupply);
 
     // Validate that the decimal precision is 0
     require!(nft_mint_account.decimals == 0, RewardError::InvalidNftDecimals);
 
-    // Manually derive the associated token account PDA
-    let (derived_ata, _bump_seed) = Pubkey::find_program_address(
-        &[
-            &ctx.accounts.user.key().to_bytes(),
-            &ctx.accounts.token_program_2022.key().to_bytes(),
-            &ctx.accounts.nft_mint_address.key().to_bytes(),
-        ],
-        &ctx.accounts.associated_token_program.key()
+    // a depositor who sold the nft leaves the nft token account out, or passes it emptied
+    let holds_nft = ctx.accounts.user_nft_token_account
+        .as_ref()
+        .is_some_and(|user_nft_token_account| user_nft_token_account.amount > 0);
+    nfnode_entry.require_deposit_owner(&ctx.accounts.user.key(), holds_nft)?;
+    let deposit_requirement = 5000000000;
+    require!(
+        amount <= nfnode_entry.deposit_amount - nfnode_entry.bonded_deposit(deposit_requirement),
+        RewardError::UnbondingRequired
     );
-
-    // Validate the ownership of the user_nft_token_account
-    require!(derived_ata == *user_nft_token_account_info.key, RewardError::InvalidNftTokenAccount);
-    let user_nft_token_account_data = user_nft_token_account_info.try_borrow_data()?;
-    let user_nft_token_account = SplToken2022Account::try_deserialize(
-        &mut &user_nft_token_account_data[..]
-    )?;
-
-    if user_nft_token_account.amount == 0 {
-        return err!(RewardError::InsufficientNftBalance);
-    }
-
-    if user_nft_token_account.mint != ctx.accounts.nft_mint_address.key() {
-        return err!(RewardError::InvalidNftMint);
-    }
-    let current_timestamp = Clock::get()?.unix_timestamp;
-    let timestamp_entry = nfnode_entry.deposit_timestamp
-        .checked_div(86400)
-        .ok_or(RewardError::ArithmeticOverflow)?;
-    let current_day = current_timestamp.checked_div(86400).ok_or(RewardError::ArithmeticOverflow)?;
-    msg!("Current day: {}", current_day);
-    msg!("Timestamp entry: {}", timestamp_entry);
-    require!(current_day > timestamp_entry + 30, RewardError::WithdrawTooEarly);
     let authority_bump = ctx.bumps.token_storage_authority;
     let binding = ctx.accounts.nft_mint_address.key();
     let authority_seeds = &[&b"token_storage"[..], binding.as_ref(), &[authority_bump]];
@@ -88,8 +74,7 @@ pub fn withdraw_tokens(ctx: Context<WithdrawTokens>) -> Result<()> {
     )?;
 
     let nfnode_entry = &mut ctx.accounts.nfnode_entry;
-    nfnode_entry.deposit_amount = 0;
-    nfnode_entry.deposit_timestamp = Clock::get()?.unix_timestamp;
+    nfnode_entry.deposit_amount -= amount;
     Ok(())
 }
 #[derive(Accounts)]
@@ -99,8 +84,13 @@ pub struct WithdrawTokens<'info> {
     pub token_mint: Account<'info, Mint>,
     ///CHECK: only read account
     pub nft_mint_address: InterfaceAccount<'info, Mint2022>,
-    /// CHECK: used to check nft ownership
-    pub user_nft_token_account: AccountInfo<'info>,
+    // left out by a depositor who no longer holds the nft
+    #[account(
+        token::mint = nft_mint_address,
+        token::authority = user,
+        token::token_program = token_program_2022
+    )]
+    pub user_nft_token_account: Option<InterfaceAccount<'info, SplToken2022Account>>,
     #[account(
         mut,
         seeds = [b"nfnode_entry", nft_mint_address.key().as_ref()],
@@ -109,6 +99,8 @@ pub struct WithdrawTokens<'info> {
     pub nfnode_entry: Box<Account<'info, NfNodeEntry>>,
     #[account(seeds = [b"admin_account"], bump)]
     pub admin_account: Account<'info, AdminAccount>,
+    // authorization record of the nft mint authority, not needed for members of the nfnode collection
+    pub mint_authority_entry: Option<Account<'info, MintAuthorityEntry>>,
     /// CHECK:
     #[account(mut, seeds = [b"token_storage",nft_mint_address.key().as_ref()], bump)]
     pub token_storage_authority: AccountInfo<'info>,
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{ self, Mint, Token, TokenAccount, Transfer },
+};
+
+use crate::{ errors::RewardError, state::{ AdminAccount, StakePosition, PAUSE_WITHDRAWALS } };
+pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let admin_account = &ctx.accounts.admin_account;
+    require!(
+        !admin_account.is_paused(PAUSE_WITHDRAWALS, current_timestamp) ||
+            admin_account.emergency_withdrawal_open(current_timestamp),
+        RewardError::ProgramPaused
+    );
+    let stake_position = &mut ctx.accounts.stake_position;
+    let amount = stake_position.unstaking_amount;
+    require!(amount > 0, RewardError::InsufficientStake);
+    require!(
+        stake_position.unstake_cooldown_elapsed(current_timestamp),
+        RewardError::UnstakeCooldownActive
+    );
+    stake_position.unstaking_amount = 0;
+
+    let nft_mint_address = ctx.accounts.nft_mint_address.key();
+    let vault_bump = ctx.bumps.stake_vault_authority;
+    let vault_seeds = &[&b"stake_vault"[..], nft_mint_address.as_ref(), &[vault_bump]];
+    let signer_seeds = &[&vault_seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault_account.to_account_info(),
+                to: ctx.accounts.delegator_token_account.to_account_info(),
+                authority: ctx.accounts.stake_vault_authority.to_account_info(),
+            },
+            signer_seeds
+        ),
+        amount
+    )?;
+    Ok(())
+}
+#[derive(Accounts)]
+pub struct WithdrawUnstaked<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    /// CHECK: only used to derive the stake accounts
+    pub nft_mint_address: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"stake_position", nft_mint_address.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+    pub token_mint: Box<Account<'info, Mint>>,
+    /// CHECK: delegated stake vault authority
+    #[account(seeds = [b"stake_vault", nft_mint_address.key().as_ref()], bump)]
+    pub stake_vault_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = stake_vault_authority,
+    )]
+    pub stake_vault_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        associated_token::mint = token_mint,
+        associated_token::authority = delegator
+    )]
+    pub delegator_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Box<Account<'info, AdminAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -4,10 +4,32 @@ use anchor_spl::{
     token::{ self, Token, TokenAccount, Transfer, Mint }, //Wayru Token
 };
 use solana_program::{ pubkey::Pubkey };
+mod claim;
+mod collection;
+mod device;
 mod errors;
+mod identity;
 mod instructions;
+mod location;
+mod pool;
+mod staking;
 mod state;
-use crate::{ errors::*, state::{ NfNodeType } };
+use crate::{
+    errors::*,
+    state::{
+        AdminAccount,
+        FunderEntry,
+        RewardPool,
+        NfNodeType,
+        ClaimRole,
+        ClaimPeriodMode,
+        LockupTier,
+        NfNodeIdentity,
+        CompressedNftProof,
+        PAUSE_ALL,
+        PAUSE_FUNDING,
+    },
+};
 declare_id!("EqeqjHyJTsmnVFCs3rnUEKSgvYBtjXa5ujJueiexWLHp");
 
 #[program]
@@ -29,9 +51,10 @@ pub mod reward_system {
     pub fn initialize_nfnode(
         ctx: Context<InitializeNfNode>,
         host_share: u64,
-        nfnode_type: NfNodeType
+        nfnode_type: NfNodeType,
+        debt_withholding_bps: Option<u16>
     ) -> Result<()> {
-        instructions::initialize_nfnode(ctx, host_share, nfnode_type)
+        instructions::initialize_nfnode(ctx, host_share, nfnode_type, debt_withholding_bps)
     }
     pub fn update_nfnode(ctx: Context<UpdateNfNode>, host_share: u64) -> Result<()> {
         instructions::update_nfnode(ctx, host_share)
@@ -40,9 +63,20 @@ pub mod reward_system {
     pub fn fund_token_storage(ctx: Context<FundTokenStorage>, amount: u64) -> Result<()> {
         // Validate that the amount is greater than zero
         require!(amount > 0, RewardError::InvalidFundingAmount);
+        require!(
+            !ctx.accounts.admin_account.is_paused(PAUSE_FUNDING, Clock::get()?.unix_timestamp),
+            RewardError::ProgramPaused
+        );
 
         token::transfer(ctx.accounts.transfer_to_token_storage(), amount)?;
-        Ok(())
+
+        let funder = ctx.accounts.user.key();
+        ctx.accounts.reward_pool.record_funding(
+            &mut ctx.accounts.funder_entry,
+            funder,
+            amount,
+            Clock::get()?.unix_timestamp
+        )
     }
 
     pub fn owner_claim_rewards(
@@ -59,14 +93,87 @@ pub mod reward_system {
     ) -> Result<()> {
         instructions::others_claim_rewards(ctx, reward_amount, nonce)
     }
+
+    pub fn host_claim_rewards(
+        ctx: Context<OthersClaimRewards>,
+        reward_amount: u64,
+        nonce: u64
+    ) -> Result<()> {
+        instructions::host_claim_rewards(ctx, reward_amount, nonce)
+    }
+
+    pub fn manufacturer_claim_rewards(
+        ctx: Context<OthersClaimRewards>,
+        reward_amount: u64,
+        nonce: u64
+    ) -> Result<()> {
+        instructions::manufacturer_claim_rewards(ctx, reward_amount, nonce)
+    }
+    pub fn initialize_host(ctx: Context<InitializeHost>) -> Result<()> {
+        instructions::initialize_host(ctx)
+    }
+    pub fn host_claim_all<'info>(
+        ctx: Context<'_, '_, 'info, 'info, HostClaimAll<'info>>,
+        reward_amounts: Vec<u64>,
+        nonce: u64
+    ) -> Result<()> {
+        instructions::host_claim_all(ctx, reward_amounts, nonce)
+    }
+    pub fn crank_claim_rewards(
+        ctx: Context<CrankClaimRewards>,
+        role: ClaimRole,
+        reward_amount: u64,
+        nonce: u64,
+        crank_fee: u64
+    ) -> Result<()> {
+        instructions::crank_claim_rewards(ctx, role, reward_amount, nonce, crank_fee)
+    }
+    pub fn set_payout_destination(
+        ctx: Context<SetPayoutDestination>,
+        role: ClaimRole,
+        destination: Pubkey
+    ) -> Result<()> {
+        instructions::set_payout_destination(ctx, role, destination)
+    }
+    pub fn set_auto_compound(
+        ctx: Context<SetAutoCompound>,
+        compound_bps: u16,
+        stake_beyond_requirement: bool
+    ) -> Result<()> {
+        instructions::set_auto_compound(ctx, compound_bps, stake_beyond_requirement)
+    }
+    pub fn migrate_admin_account<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateAdminAccount<'info>>
+    ) -> Result<()> {
+        instructions::migrate_admin_account(ctx)
+    }
+    pub fn migrate_nfnode_entry(ctx: Context<MigrateNfNodeEntry>) -> Result<()> {
+        instructions::migrate_nfnode_entry(ctx)
+    }
+    pub fn migrate_reward_entry(ctx: Context<MigrateRewardEntry>) -> Result<()> {
+        instructions::migrate_reward_entry(ctx)
+    }
+    pub fn migrate_host(ctx: Context<MigrateHost>) -> Result<()> {
+        instructions::migrate_host(ctx)
+    }
+    pub fn migrate_payout_config(ctx: Context<MigratePayoutConfig>) -> Result<()> {
+        instructions::migrate_payout_config(ctx)
+    }
+    pub fn migrate_auto_compound(ctx: Context<MigrateAutoCompound>) -> Result<()> {
+        instructions::migrate_auto_compound(ctx)
+    }
     pub fn pause_program(ctx: Context<UpdateAdmin>) -> Result<()> {
         let admin_account = &mut ctx.accounts.admin_account;
         require!(
             ctx.accounts.user.key() == admin_account.admin_pubkey,
             RewardError::UnauthorizedAdmin
         );
-        require!(admin_account.paused == false, RewardError::AlreadyPaused);
-        admin_account.paused = true;
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            admin_account.active_pause_flags(current_timestamp) != PAUSE_ALL,
+            RewardError::AlreadyPaused
+        );
+        admin_account.set_pause(PAUSE_ALL, 0, 0, current_timestamp);
         Ok(())
     }
 
@@ -76,23 +183,331 @@ pub mod reward_system {
             ctx.accounts.user.key() == admin_account.admin_pubkey,
             RewardError::UnauthorizedAdmin
         );
-        require!(admin_account.paused == true, RewardError::AlreadyRunning);
-        admin_account.paused = false;
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            admin_account.active_pause_flags(current_timestamp) != 0,
+            RewardError::AlreadyRunning
+        );
+        admin_account.set_pause(0, 0, 0, current_timestamp);
         Ok(())
     }
-    pub fn deposit_tokens(ctx: Context<DepositTokens>) -> Result<()> {
-        instructions::deposit_tokens(ctx)
+    pub fn deposit_tokens(
+        ctx: Context<DepositTokens>,
+        amount: u64,
+        lockup_tier: LockupTier
+    ) -> Result<()> {
+        instructions::deposit_tokens(ctx, amount, lockup_tier)
+    }
+    pub fn withdraw_tokens(ctx: Context<WithdrawTokens>, amount: u64) -> Result<()> {
+        instructions::withdraw_tokens(ctx, amount)
+    }
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>) -> Result<()> {
+        instructions::request_withdrawal(ctx)
+    }
+    pub fn cancel_withdrawal(ctx: Context<RequestWithdrawal>) -> Result<()> {
+        instructions::cancel_withdrawal(ctx)
     }
-    pub fn withdraw_tokens(ctx: Context<WithdrawTokens>) -> Result<()> {
-        instructions::withdraw_tokens(ctx)
+    pub fn complete_withdrawal(ctx: Context<CompleteWithdrawal>) -> Result<()> {
+        instructions::complete_withdrawal(ctx)
     }
-    pub fn add_mint_authority(ctx: Context<AddMintAuthority>, new_mint_authority: Pubkey) -> Result<()> {
-        instructions::add_mint_authority(ctx, new_mint_authority)
+    pub fn set_deposit_carry(ctx: Context<SetDepositCarry>, carries_with_nft: bool) -> Result<()> {
+        instructions::set_deposit_carry(ctx, carries_with_nft)
+    }
+    pub fn add_mint_authority(
+        ctx: Context<AddMintAuthority>,
+        new_mint_authority: Pubkey,
+        registration_opens_at: i64,
+        registration_closes_at: i64,
+        allowed_nfnode_types: Vec<NfNodeType>
+    ) -> Result<()> {
+        instructions::add_mint_authority(
+            ctx,
+            new_mint_authority,
+            registration_opens_at,
+            registration_closes_at,
+            allowed_nfnode_types
+        )
     }
 
     pub fn remove_mint_authority(ctx: Context<RemoveMintAuthority>, mint_authority: Pubkey) -> Result<()> {
         instructions::remove_mint_authority(ctx, mint_authority)
     }
+
+    pub fn add_identity_source(
+        ctx: Context<AddIdentitySource>,
+        source: Pubkey,
+        identity: NfNodeIdentity
+    ) -> Result<()> {
+        instructions::add_identity_source(ctx, source, identity)
+    }
+
+    pub fn remove_identity_source(ctx: Context<RemoveIdentitySource>, source: Pubkey) -> Result<()> {
+        instructions::remove_identity_source(ctx, source)
+    }
+
+    pub fn initialize_asset_nfnode<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeAssetNfNode<'info>>,
+        host_share: u64,
+        nfnode_type: NfNodeType,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::initialize_asset_nfnode(ctx, host_share, nfnode_type, compressed_nft_proof)
+    }
+
+    pub fn asset_owner_claim_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetOwnerClaimRewards<'info>>,
+        reward_amount: u64,
+        nonce: u64,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_owner_claim_rewards(ctx, reward_amount, nonce, compressed_nft_proof)
+    }
+
+    pub fn asset_deposit_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetDepositTokens<'info>>,
+        amount: u64,
+        lockup_tier: LockupTier,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_deposit_tokens(ctx, amount, lockup_tier, compressed_nft_proof)
+    }
+
+    pub fn asset_withdraw_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetWithdrawTokens<'info>>,
+        amount: u64,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_withdraw_tokens(ctx, amount, compressed_nft_proof)
+    }
+
+    pub fn asset_set_payout_destination<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetSetPayoutDestination<'info>>,
+        role: ClaimRole,
+        destination: Pubkey,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_set_payout_destination(ctx, role, destination, compressed_nft_proof)
+    }
+
+    pub fn asset_set_auto_compound<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetSetAutoCompound<'info>>,
+        compound_bps: u16,
+        stake_beyond_requirement: bool,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_set_auto_compound(
+            ctx,
+            compound_bps,
+            stake_beyond_requirement,
+            compressed_nft_proof
+        )
+    }
+
+    pub fn asset_set_stake_commission<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetSetStakeCommission<'info>>,
+        commission_bps: u16,
+        stake_cap: u64,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_set_stake_commission(ctx, commission_bps, stake_cap, compressed_nft_proof)
+    }
+
+    pub fn asset_set_deposit_carry<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetSetDepositCarry<'info>>,
+        carries_with_nft: bool,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_set_deposit_carry(ctx, carries_with_nft, compressed_nft_proof)
+    }
+
+    pub fn asset_request_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetRequestWithdrawal<'info>>,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_request_withdrawal(ctx, compressed_nft_proof)
+    }
+
+    pub fn asset_cancel_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetRequestWithdrawal<'info>>,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_cancel_withdrawal(ctx, compressed_nft_proof)
+    }
+
+    pub fn asset_complete_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetCompleteWithdrawal<'info>>,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_complete_withdrawal(ctx, compressed_nft_proof)
+    }
+
+    pub fn asset_crank_claim_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssetCrankClaimRewards<'info>>,
+        role: ClaimRole,
+        reward_amount: u64,
+        nonce: u64,
+        crank_fee: u64,
+        compressed_nft_proof: Option<CompressedNftProof>
+    ) -> Result<()> {
+        instructions::asset_crank_claim_rewards(
+            ctx,
+            role,
+            reward_amount,
+            nonce,
+            crank_fee,
+            compressed_nft_proof
+        )
+    }
+
+    pub fn bind_device(ctx: Context<BindDevice>, device_pubkey: Pubkey) -> Result<()> {
+        instructions::bind_device(ctx, device_pubkey)
+    }
+
+    pub fn rebind_device(ctx: Context<RebindDevice>, device_pubkey: Pubkey) -> Result<()> {
+        instructions::rebind_device(ctx, device_pubkey)
+    }
+
+    pub fn submit_heartbeat(ctx: Context<SubmitHeartbeat>, timestamp: i64) -> Result<()> {
+        instructions::submit_heartbeat(ctx, timestamp)
+    }
+
+    pub fn assert_location(ctx: Context<AssertLocation>, h3_cell: u64) -> Result<()> {
+        instructions::assert_location(ctx, h3_cell)
+    }
+
+    pub fn set_h3_region_multiplier(
+        ctx: Context<SetH3RegionMultiplier>,
+        region_cell: u64,
+        multiplier_bps: u16
+    ) -> Result<()> {
+        instructions::set_h3_region_multiplier(ctx, region_cell, multiplier_bps)
+    }
+
+    pub fn initialize_reward_pool(
+        ctx: Context<InitializeRewardPool>,
+        protocol_fee_bps: u16,
+        burn_bps: u16
+    ) -> Result<()> {
+        instructions::initialize_reward_pool(ctx, protocol_fee_bps, burn_bps)
+    }
+
+    pub fn set_reward_pool_fees(
+        ctx: Context<SetRewardPoolFees>,
+        protocol_fee_bps: u16,
+        burn_bps: u16
+    ) -> Result<()> {
+        instructions::set_reward_pool_fees(ctx, protocol_fee_bps, burn_bps)
+    }
+
+    pub fn create_reward_stream(
+        ctx: Context<CreateRewardStream>,
+        amount: u64,
+        rate_per_second: u64
+    ) -> Result<()> {
+        instructions::create_reward_stream(ctx, amount, rate_per_second)
+    }
+    pub fn drip(ctx: Context<Drip>) -> Result<()> {
+        instructions::drip(ctx)
+    }
+    pub fn close_reward_stream(ctx: Context<CloseRewardStream>) -> Result<()> {
+        instructions::close_reward_stream(ctx)
+    }
+    pub fn set_reserve_ratio(ctx: Context<SetReserveRatio>, reserve_ratio_bps: u16) -> Result<()> {
+        instructions::set_reserve_ratio(ctx, reserve_ratio_bps)
+    }
+    pub fn claim_owed(ctx: Context<ClaimOwed>, role: ClaimRole) -> Result<()> {
+        instructions::claim_owed(ctx, role)
+    }
+    pub fn set_stake_commission(
+        ctx: Context<SetStakeCommission>,
+        commission_bps: u16,
+        stake_cap: u64
+    ) -> Result<()> {
+        instructions::set_stake_commission(ctx, commission_bps, stake_cap)
+    }
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        instructions::stake(ctx, amount)
+    }
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        instructions::unstake(ctx, amount)
+    }
+    pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+        instructions::withdraw_unstaked(ctx)
+    }
+    pub fn claim_stake_rewards(ctx: Context<ClaimStakeRewards>) -> Result<()> {
+        instructions::claim_stake_rewards(ctx)
+    }
+    pub fn freeze_nfnode(ctx: Context<FreezeNfNode>, reason: u16) -> Result<()> {
+        instructions::freeze_nfnode(ctx, reason)
+    }
+
+    pub fn unfreeze_nfnode(ctx: Context<FreezeNfNode>) -> Result<()> {
+        instructions::unfreeze_nfnode(ctx)
+    }
+
+    pub fn appeal_nfnode_freeze(ctx: Context<AppealNfNodeFreeze>) -> Result<()> {
+        instructions::appeal_nfnode_freeze(ctx)
+    }
+
+    pub fn blacklist_wallet(ctx: Context<BlacklistWallet>, wallet: Pubkey, reason: u16) -> Result<()> {
+        instructions::blacklist_wallet(ctx, wallet, reason)
+    }
+
+    pub fn remove_blacklisted_wallet(
+        ctx: Context<RemoveBlacklistedWallet>,
+        wallet: Pubkey
+    ) -> Result<()> {
+        instructions::remove_blacklisted_wallet(ctx, wallet)
+    }
+
+    pub fn appeal_blacklist(ctx: Context<AppealBlacklist>) -> Result<()> {
+        instructions::appeal_blacklist(ctx)
+    }
+
+    pub fn set_pause_flags(
+        ctx: Context<UpdateAdmin>,
+        pause_flags: u8,
+        pause_reason: u16,
+        pause_expires_at: i64
+    ) -> Result<()> {
+        instructions::set_pause_flags(ctx, pause_flags, pause_reason, pause_expires_at)
+    }
+
+    pub fn set_emergency_withdrawal_grace_period(
+        ctx: Context<UpdateAdmin>,
+        grace_period: i64
+    ) -> Result<()> {
+        instructions::set_emergency_withdrawal_grace_period(ctx, grace_period)
+    }
+
+    pub fn set_claim_period(
+        ctx: Context<SetClaimPeriod>,
+        mode: ClaimPeriodMode,
+        period_seconds: i64,
+        offset_seconds: i64
+    ) -> Result<()> {
+        instructions::set_claim_period(ctx, mode, period_seconds, offset_seconds)
+    }
+
+    pub fn set_nfnode_collection(ctx: Context<SetNfNodeCollection>) -> Result<()> {
+        instructions::set_nfnode_collection(ctx)
+    }
+
+    pub fn set_mint_authority_grant(
+        ctx: Context<SetMintAuthorityGrant>,
+        mint_authority: Pubkey,
+        registration_opens_at: i64,
+        registration_closes_at: i64,
+        allowed_nfnode_types: Vec<NfNodeType>
+    ) -> Result<()> {
+        instructions::set_mint_authority_grant(
+            ctx,
+            mint_authority,
+            registration_opens_at,
+            registration_closes_at,
+            allowed_nfnode_types
+        )
+    }
 }
 pub struct NfnodeRewards;
 
@@ -126,6 +541,18 @@ pub struct FundTokenStorage<'info> {
         associated_token::authority = user,
     )]
     pub user_token_account: Account<'info, TokenAccount>,
+    #[account(seeds = [b"admin_account"], bump)]
+    pub admin_account: Account<'info, AdminAccount>,
+    #[account(mut, seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FunderEntry::INIT_SPACE,
+        seeds = [b"funder", user.key().as_ref()],
+        bump
+    )]
+    pub funder_entry: Account<'info, FunderEntry>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
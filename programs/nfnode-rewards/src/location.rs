@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::RewardError;
+// H3 index layout: reserved bit, 4 mode bits, 3 reserved bits, 4 resolution bits,
+// 7 base cell bits and 15 digits of 3 bits, unused digits set to 7
+const H3_CELL_MODE: u64 = 1;
+const H3_MODE_OFFSET: u64 = 59;
+const H3_RESOLUTION_OFFSET: u64 = 52;
+const H3_MAX_RESOLUTION: u8 = 15;
+const H3_DIGIT_BITS: u64 = 3;
+const H3_UNUSED_DIGIT: u64 = 7;
+
+pub fn h3_resolution(h3_cell: u64) -> u8 {
+    ((h3_cell >> H3_RESOLUTION_OFFSET) & 0xf) as u8
+}
+
+// only checks the header bits, the cell itself is trusted to the asserting party
+pub fn validate_h3_cell(h3_cell: u64, min_resolution: u8) -> Result<()> {
+    require!(h3_cell >> 63 == 0, RewardError::InvalidH3Cell);
+    require!((h3_cell >> H3_MODE_OFFSET) & 0xf == H3_CELL_MODE, RewardError::InvalidH3Cell);
+    require!(h3_resolution(h3_cell) >= min_resolution, RewardError::InvalidH3Cell);
+    Ok(())
+}
+
+pub fn h3_parent(h3_cell: u64, resolution: u8) -> Result<u64> {
+    let cell_resolution = h3_resolution(h3_cell);
+    require!(resolution <= cell_resolution, RewardError::InvalidH3Cell);
+    let mut parent =
+        (h3_cell & !(0xf << H3_RESOLUTION_OFFSET)) | ((resolution as u64) << H3_RESOLUTION_OFFSET);
+    for digit in resolution + 1..=H3_MAX_RESOLUTION {
+        parent |= H3_UNUSED_DIGIT << (((H3_MAX_RESOLUTION - digit) as u64) * H3_DIGIT_BITS);
+    }
+    Ok(parent)
+}
+
+pub fn apply_multiplier(reward_amount: u64, multiplier_bps: u16) -> Result<u64> {
+    (reward_amount as u128)
+        .checked_mul(multiplier_bps as u128)
+        .and_then(|amount| amount.checked_div(10000))
+        .and_then(|amount| u64::try_from(amount).ok())
+        .ok_or(error!(RewardError::ArithmeticOverflow))
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ self, Burn, Transfer };
+
+use crate::{ errors::RewardError, state::RewardPool };
+// token accounts moving the protocol cut out of the token_storage reward pool
+pub struct ProtocolCutAccounts<'info> {
+    pub token_program: AccountInfo<'info>,
+    pub token_mint: AccountInfo<'info>,
+    pub token_storage_account: AccountInfo<'info>,
+    pub token_storage_authority: AccountInfo<'info>,
+    pub treasury_token_account: Option<AccountInfo<'info>>,
+}
+
+// records a claim paid by the reward pool, sends the protocol fee to the treasury and burns
+// the burn share, returns what is left for the claimer. reward_amount is everything the claim
+// takes out of the pool, so the delegator share, crank fee and the amounts later escrowed for
+// debt repayment or auto-compounding are counted in total_claimed here
+pub fn collect_protocol_cut<'info>(
+    reward_pool: &mut RewardPool,
+    reward_amount: u64,
+    accounts: ProtocolCutAccounts<'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<u64> {
+    let (protocol_fee, burn_amount) = reward_pool.protocol_cut(reward_amount)?;
+    if protocol_fee > 0 {
+        let treasury_token_account = accounts.treasury_token_account
+            .ok_or(RewardError::InvalidTreasuryTokenAccount)?;
+        require_keys_eq!(
+            treasury_token_account.key(),
+            reward_pool.treasury_token_account,
+            RewardError::InvalidTreasuryTokenAccount
+        );
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.clone(),
+                Transfer {
+                    from: accounts.token_storage_account.clone(),
+                    to: treasury_token_account,
+                    authority: accounts.token_storage_authority.clone(),
+                },
+                signer_seeds
+            ),
+            protocol_fee
+        )?;
+    }
+    if burn_amount > 0 {
+        token::burn(
+            CpiContext::new_with_signer(
+                accounts.token_program,
+                Burn {
+                    mint: accounts.token_mint,
+                    from: accounts.token_storage_account,
+                    authority: accounts.token_storage_authority,
+                },
+                signer_seeds
+            ),
+            burn_amount
+        )?;
+    }
+
+    reward_pool.total_claimed = reward_pool.total_claimed
+        .checked_add(reward_amount)
+        .ok_or(RewardError::ArithmeticOverflow)?;
+    reward_pool.total_protocol_fees = reward_pool.total_protocol_fees
+        .checked_add(protocol_fee)
+        .ok_or(RewardError::ArithmeticOverflow)?;
+    reward_pool.total_burned = reward_pool.total_burned
+        .checked_add(burn_amount)
+        .ok_or(RewardError::ArithmeticOverflow)?;
+    Ok(reward_amount - protocol_fee - burn_amount)
+}
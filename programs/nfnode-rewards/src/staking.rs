@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{ self, Transfer };
+
+use crate::{ errors::RewardError, state::NfNodeStake };
+// token accounts moving the delegator share of an owner reward into the stake vault
+pub struct DelegatorShareAccounts<'info> {
+    pub token_program: AccountInfo<'info>,
+    pub token_storage_account: AccountInfo<'info>,
+    pub token_storage_authority: AccountInfo<'info>,
+    pub nfnode_stake: AccountInfo<'info>,
+    pub stake_vault_account: Option<AccountInfo<'info>>,
+}
+
+// pays the delegators of an nfnode their share of an owner reward, returns what they received
+pub fn distribute_delegator_share<'info>(
+    reward_amount: u64,
+    deposit_amount: u64,
+    accounts: DelegatorShareAccounts<'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> Result<u64> {
+    if accounts.nfnode_stake.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(
+        *accounts.nfnode_stake.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    let mut nfnode_stake = NfNodeStake::try_deserialize(
+        &mut &accounts.nfnode_stake.try_borrow_data()?[..]
+    )?;
+    nfnode_stake.apply_pending_commission(Clock::get()?.unix_timestamp);
+    let (delegator_amount, _) = nfnode_stake.delegator_share(reward_amount, deposit_amount)?;
+    if delegator_amount == 0 {
+        return Ok(0);
+    }
+    let stake_vault_account = accounts.stake_vault_account.ok_or(
+        RewardError::StakeVaultRequired
+    )?;
+    token::transfer(
+        CpiContext::new_with_signer(
+            accounts.token_program,
+            Transfer {
+                from: accounts.token_storage_account,
+                to: stake_vault_account,
+                authority: accounts.token_storage_authority,
+            },
+            signer_seeds
+        ),
+        delegator_amount
+    )?;
+
+    nfnode_stake.distribute(delegator_amount)?;
+    let mut nfnode_stake_data = accounts.nfnode_stake.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut nfnode_stake_data[..];
+    nfnode_stake.try_serialize(&mut writer)?;
+    Ok(delegator_amount)
+}
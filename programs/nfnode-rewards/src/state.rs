@@ -1,19 +1,129 @@
-use anchor_lang::prelude::*;
-pub const MAX_MINT_AUTHORITIES: usize = 10; //
+use anchor_lang::{ prelude::*, system_program };
+// claims of device bound nfnodes need a heartbeat within this many seconds
+pub const HEARTBEAT_MAX_AGE: i64 = 86400;
+// claim period used until the admin configures one
+pub const DEFAULT_CLAIM_PERIOD: i64 = 86400;
+// a pause longer than this lets nfnode holders withdraw their deposits
+pub const DEFAULT_EMERGENCY_WITHDRAWAL_GRACE_PERIOD: i64 = 30 * 86400;
+pub const MAX_EMERGENCY_WITHDRAWAL_GRACE_PERIOD: i64 = 90 * 86400;
+// subsystems that can be paused independently, AdminAccount.pause_flags bits
+pub const PAUSE_CLAIMS: u8 = 1 << 0;
+pub const PAUSE_DEPOSITS: u8 = 1 << 1;
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 2;
+pub const PAUSE_REGISTRATION: u8 = 1 << 3;
+pub const PAUSE_FUNDING: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 =
+    PAUSE_CLAIMS | PAUSE_DEPOSITS | PAUSE_WITHDRAWALS | PAUSE_REGISTRATION | PAUSE_FUNDING;
+// protocol fee and burn together can take at most half of a claim
+pub const MAX_PROTOCOL_CUT_BPS: u16 = 5000;
+// new claims can always draw on at least a tenth of the pool
+pub const MAX_RESERVE_RATIO_BPS: u16 = 9000;
+// an unbonding deposit is released this long after request_withdrawal
+pub const DEPOSIT_UNBONDING_PERIOD: i64 = 7 * 86400;
+// delegated stake stays locked this long after unstaking
+pub const UNSTAKE_COOLDOWN: i64 = 7 * 86400;
+// a commission increase applies this long after it is set, delegators can unstake before it does
+pub const STAKE_COMMISSION_DELAY: i64 = 7 * 86400;
+// new stake only earns a share of owner rewards once it has been staked this long
+pub const MIN_STAKE_AGE: i64 = 7 * 86400;
+// auto-compounding only stands in for the deposit at a real share of the owner's claims
+pub const MIN_DEPOSIT_COMPOUND_BPS: u16 = 5000;
+// and only for this long after opting in, claims need a covered deposit afterwards
+pub const AUTO_COMPOUND_COVERAGE_PERIOD: i64 = 180 * 86400;
+// fixed point scale of NfNodeStake.reward_per_token
+pub const STAKE_REWARD_PRECISION: u128 = 1_000_000_000_000;
+// 10 WAYRU paid into the reward pool on every location assertion
+pub const LOCATION_ASSERTION_FEE: u64 = 10000000;
+// an nfnode location can be re-asserted once a week
+pub const LOCATION_ASSERTION_COOLDOWN: i64 = 7 * 86400;
+// resolution of the H3 cells reward multipliers are configured for
+pub const H3_REGION_RESOLUTION: u8 = 5;
+pub const MAX_REGION_MULTIPLIER_BPS: u16 = 30000;
 #[account]
+#[derive(InitSpace)]
 pub struct RewardEntry {
+    pub version: u8,
     pub last_claimed_nonce: u64,
     pub last_claimed_timestamp: i64,
     pub total_rewards_earned: u64,
+    // unpaid remainder of owner claims the pool could not fully cover, paid by claim_owed. entries
+    // from before owed rewards were tracked per role hold what every role was owed here
+    pub owed_amount: u64,
+    pub owed_host_amount: u64,
+    pub owed_manufacturer_amount: u64,
+    pub reserved: [u8; 8],
 }
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+impl RewardEntry {
+    pub const VERSION: u8 = 1;
+
+    pub fn owed_amount_mut(&mut self, role: &ClaimRole) -> &mut u64 {
+        match role {
+            ClaimRole::Owner => &mut self.owed_amount,
+            ClaimRole::Host => &mut self.owed_host_amount,
+            ClaimRole::Manufacturer => &mut self.owed_manufacturer_amount,
+        }
+    }
+}
+// deposit lockup chosen at deposit time, longer lockups boost owner rewards
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
+pub enum LockupTier {
+    Days30,
+    Days90,
+    Days180,
+    Days365,
+}
+impl LockupTier {
+    pub fn lockup_days(&self) -> i64 {
+        match self {
+            LockupTier::Days30 => 30,
+            LockupTier::Days90 => 90,
+            LockupTier::Days180 => 180,
+            LockupTier::Days365 => 365,
+        }
+    }
+    pub fn reward_multiplier_bps(&self) -> u16 {
+        match self {
+            LockupTier::Days30 => 10000,
+            LockupTier::Days90 => 11000,
+            LockupTier::Days180 => 12500,
+            LockupTier::Days365 => 15000,
+        }
+    }
+}
+#[allow(clippy::upper_case_acronyms)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
 pub enum NfNodeType {
     DON,
     BYOD,
     WayruHotspot,
 }
+impl NfNodeType {
+    pub const ALL_FLAGS: u8 = 0b111;
+
+    pub fn flag(&self) -> u8 {
+        match self {
+            NfNodeType::DON => 1 << 0,
+            NfNodeType::BYOD => 1 << 1,
+            NfNodeType::WayruHotspot => 1 << 2,
+        }
+    }
+    pub fn flags(nfnode_types: &[NfNodeType]) -> u8 {
+        nfnode_types.iter().fold(0, |flags, nfnode_type| flags | nfnode_type.flag())
+    }
+    // device type as written in the nft metadata
+    pub fn from_device_type(device_type: &str) -> Option<NfNodeType> {
+        match device_type.to_ascii_lowercase().as_str() {
+            "don" => Some(NfNodeType::DON),
+            "byod" => Some(NfNodeType::BYOD),
+            "wayru_hotspot" => Some(NfNodeType::WayruHotspot),
+            _ => None,
+        }
+    }
+}
 #[account]
+#[derive(InitSpace)]
 pub struct NfNodeEntry {
+    pub version: u8,
     pub owner_last_claimed_timestamp: i64,
     pub host: Pubkey,
     pub host_share: u64,
@@ -24,13 +134,954 @@ pub struct NfNodeEntry {
     pub deposit_amount: u64,
     pub deposit_timestamp: i64,
     pub nfnode_type: NfNodeType,
+    // financed part of the deposit still to be repaid from owner claims
+    pub deposit_debt: u64,
+    pub debt_withholding_bps: u16,
+    // backend proving ownership of the nfnode nft, keyed by nft_mint_address in every seed
+    pub identity: NfNodeIdentity,
+    // hardware key bound by the manufacturer, default when no device is bound
+    pub device_pubkey: Pubkey,
+    pub last_heartbeat_timestamp: i64,
+    // frozen by the admin, blocks claims, deposits and withdrawals of this nfnode
+    pub frozen: bool,
+    pub freeze_reason: u16,
+    pub freeze_appealed: bool,
+    // lockup of the current deposit, entries from before lockups read as the 30 day tier
+    pub lockup_tier: LockupTier,
+    // when request_withdrawal started unbonding the deposit, 0 when bonded
+    pub unbonding_started_at: i64,
+    // wallet the deposit is released to, a deposit set to carry with the nft goes to its holder instead
+    pub depositor: Pubkey,
+    pub deposit_carries_with_nft: bool,
+    pub reserved: [u8; 32],
+}
+impl NfNodeEntry {
+    pub const VERSION: u8 = 2;
+
+    // the lockup boost only applies while the whole required deposit is locked and repaid,
+    // DON deposits are never locked
+    pub fn lockup_multiplier_bps(&self, deposit_requirement: u64) -> u16 {
+        if self.deposit_debt > 0 || self.bonded_deposit(deposit_requirement) < deposit_requirement {
+            return 10000;
+        }
+        self.lockup_tier.reward_multiplier_bps()
+    }
+
+    // a financed deposit counts as covered while its debt is being repaid
+    pub fn deposit_covered(&self, deposit_requirement: u64) -> bool {
+        self.deposit_amount.saturating_add(self.deposit_debt) >= deposit_requirement
+    }
+    // DON nodes are deployed without a deposit, so their hosts and manufacturers are never gated on it
+    pub fn role_deposit_covered(&self, deposit_requirement: u64) -> bool {
+        self.nfnode_type == NfNodeType::DON || self.deposit_covered(deposit_requirement)
+    }
+    // owners claim once the deposit is covered or auto-compounding is filling it during the coverage
+    // period, the other roles once it is covered
+    pub fn claim_deposit_covered(
+        &self,
+        role: &ClaimRole,
+        auto_compound: Option<&AutoCompound>,
+        deposit_requirement: u64,
+        now: i64
+    ) -> bool {
+        let compound_waiver = auto_compound.is_some_and(|auto_compound| {
+            *role == ClaimRole::Owner && auto_compound.waives_deposit(now)
+        });
+        self.role_deposit_covered(deposit_requirement) || compound_waiver
+    }
+    // the part of the deposit held back for unbonding, anything above it can be withdrawn at any time
+    pub fn bonded_deposit(&self, deposit_requirement: u64) -> u64 {
+        if self.nfnode_type == NfNodeType::DON {
+            return 0;
+        }
+        self.deposit_amount.min(deposit_requirement)
+    }
+    // resolves the role of a host or manufacturer claimer, host first when both are the same wallet
+    pub fn others_claim_role(&self, user: &Pubkey) -> Result<ClaimRole> {
+        if *user == self.host {
+            Ok(ClaimRole::Host)
+        } else if *user == self.manufacturer {
+            Ok(ClaimRole::Manufacturer)
+        } else {
+            err!(crate::errors::RewardError::UnauthorizedUser)
+        }
+    }
+    pub fn role_beneficiary(&self, role: &ClaimRole) -> Option<Pubkey> {
+        match role {
+            ClaimRole::Owner => None,
+            ClaimRole::Host => Some(self.host),
+            ClaimRole::Manufacturer => Some(self.manufacturer),
+        }
+    }
+    pub fn role_last_claimed_timestamp(&self, role: &ClaimRole) -> i64 {
+        match role {
+            ClaimRole::Owner => self.owner_last_claimed_timestamp,
+            ClaimRole::Host => self.host_last_claimed_timestamp,
+            ClaimRole::Manufacturer => self.manufacturer_last_claimed_timestamp,
+        }
+    }
+    pub fn set_role_last_claimed_timestamp(&mut self, role: &ClaimRole, timestamp: i64) {
+        match role {
+            ClaimRole::Owner => {
+                self.owner_last_claimed_timestamp = timestamp;
+            }
+            ClaimRole::Host => {
+                self.host_last_claimed_timestamp = timestamp;
+            }
+            ClaimRole::Manufacturer => {
+                self.manufacturer_last_claimed_timestamp = timestamp;
+            }
+        }
+    }
+    // claims stop while the deposit is unbonding
+    pub fn unbonding(&self) -> bool {
+        self.unbonding_started_at != 0
+    }
+    pub fn deposit_belongs_to(&self, user: &Pubkey, holds_nft: bool) -> bool {
+        if self.deposit_carries_with_nft { holds_nft } else { *user == self.depositor }
+    }
+    // the depositor can withdraw without the nft, anyone else has to hold it and be owed the deposit
+    pub fn require_deposit_owner(&self, user: &Pubkey, holds_nft: bool) -> Result<()> {
+        if !self.deposit_carries_with_nft && *user == self.depositor {
+            return Ok(());
+        }
+        require!(holds_nft, crate::errors::RewardError::InsufficientNftBalance);
+        require!(self.deposit_carries_with_nft, crate::errors::RewardError::NotDepositor);
+        Ok(())
+    }
+    // whoever sets the carry owns the deposit, a sale of the nft then hands it to the buyer or not
+    pub fn set_deposit_carry(&mut self, depositor: &Pubkey, carries_with_nft: bool) -> Result<()> {
+        require!(self.deposit_amount > 0, crate::errors::RewardError::DepositRequired);
+        // the payout of an unbonding deposit is settled by the record it was requested under
+        require!(!self.unbonding(), crate::errors::RewardError::NfNodeUnbonding);
+        // a financed deposit is repaid by the nft holder's claims, so it follows the nft until cleared
+        require!(self.deposit_debt == 0, crate::errors::RewardError::DepositDebtOutstanding);
+        self.depositor = *depositor;
+        self.deposit_carries_with_nft = carries_with_nft;
+        Ok(())
+    }
+    // starts unbonding the whole deposit, past the emergency withdrawal grace period neither the
+    // lockup nor a freeze holds it
+    pub fn request_unbonding(
+        &mut self,
+        emergency_withdrawal_open: bool,
+        current_timestamp: i64
+    ) -> Result<()> {
+        require!(self.deposit_amount > 0, crate::errors::RewardError::WithdrawAlreadyMade);
+        require!(!self.unbonding(), crate::errors::RewardError::NfNodeUnbonding);
+        // repayments of a financed deposit stay in escrow until the debt is cleared
+        require!(self.deposit_debt == 0, crate::errors::RewardError::DepositDebtOutstanding);
+        if !emergency_withdrawal_open {
+            require!(!self.frozen, crate::errors::RewardError::NfNodeFrozen);
+            require!(
+                self.lockup_elapsed(current_timestamp)?,
+                crate::errors::RewardError::WithdrawTooEarly
+            );
+        }
+        self.unbonding_started_at = current_timestamp;
+        Ok(())
+    }
+    pub fn cancel_unbonding(&mut self) -> Result<()> {
+        require!(self.unbonding(), crate::errors::RewardError::NotUnbonding);
+        self.unbonding_started_at = 0;
+        Ok(())
+    }
+    // the unbonding period is over, or the emergency withdrawal grace period releases the deposit
+    // right away, frozen or not
+    pub fn require_unbonded(
+        &self,
+        emergency_withdrawal_open: bool,
+        current_timestamp: i64
+    ) -> Result<()> {
+        if emergency_withdrawal_open {
+            require!(self.deposit_amount > 0, crate::errors::RewardError::WithdrawAlreadyMade);
+            require!(self.deposit_debt == 0, crate::errors::RewardError::DepositDebtOutstanding);
+        } else {
+            require!(self.unbonding(), crate::errors::RewardError::NotUnbonding);
+            require!(!self.frozen, crate::errors::RewardError::NfNodeFrozen);
+            require!(
+                current_timestamp >=
+                    self.unbonding_started_at.saturating_add(DEPOSIT_UNBONDING_PERIOD),
+                crate::errors::RewardError::UnbondingPeriodActive
+            );
+        }
+        Ok(())
+    }
+    // clears the deposit record once the whole deposit is paid out
+    pub fn release_deposit(&mut self, current_timestamp: i64) {
+        self.deposit_amount = 0;
+        self.lockup_tier = LockupTier::Days30;
+        self.unbonding_started_at = 0;
+        self.deposit_timestamp = current_timestamp;
+        self.depositor = Pubkey::default();
+        self.deposit_carries_with_nft = false;
+    }
+    // rewards only compound into a deposit their claimer would get back
+    pub fn deposit_compounds_for(&self, owner: &Pubkey) -> bool {
+        self.deposit_amount == 0 || self.deposit_belongs_to(owner, true)
+    }
+    // a top-up can extend the lockup of the held deposit, never shorten it
+    pub fn require_deposit_open(&self, lockup_tier: &LockupTier) -> Result<()> {
+        require!(
+            self.deposit_amount == 0 || lockup_tier.lockup_days() >= self.lockup_tier.lockup_days(),
+            crate::errors::RewardError::LockupTierDowngrade
+        );
+        require!(!self.frozen, crate::errors::RewardError::NfNodeFrozen);
+        require!(!self.unbonding(), crate::errors::RewardError::NfNodeUnbonding);
+        Ok(())
+    }
+    // the lockup runs from the first deposit, a top-up only restarts it when it moves to a longer tier
+    pub fn record_deposit(
+        &mut self,
+        depositor: &Pubkey,
+        amount: u64,
+        lockup_tier: LockupTier,
+        current_timestamp: i64
+    ) -> Result<()> {
+        if self.deposit_amount == 0 || lockup_tier != self.lockup_tier {
+            self.deposit_timestamp = current_timestamp;
+        }
+        if self.deposit_amount == 0 {
+            // a new deposit stays with whoever made it unless set to carry with the nft
+            self.depositor = *depositor;
+            self.deposit_carries_with_nft = false;
+        }
+        self.deposit_amount = self.deposit_amount
+            .checked_add(amount)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        // deposits pay down a financed deposit's debt first
+        self.deposit_debt = self.deposit_debt.saturating_sub(amount);
+        self.lockup_tier = lockup_tier;
+        Ok(())
+    }
+    pub fn lockup_elapsed(&self, current_timestamp: i64) -> Result<bool> {
+        let timestamp_entry = self.deposit_timestamp
+            .checked_div(86400)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        let current_day = current_timestamp
+            .checked_div(86400)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        Ok(current_day > timestamp_entry + self.lockup_tier.lockup_days())
+    }
+    // nfnodes without a bound device are not gated on heartbeats
+    pub fn device_online(&self, current_timestamp: i64) -> bool {
+        self.device_pubkey == Pubkey::default() ||
+            current_timestamp.saturating_sub(self.last_heartbeat_timestamp) <= HEARTBEAT_MAX_AGE
+    }
+    pub fn debt_withholding_amount(&self, reward_amount: u64) -> Result<u64> {
+        let withholding_amount = (reward_amount as u128)
+            .checked_mul(self.debt_withholding_bps as u128)
+            .and_then(|amount| amount.checked_div(10000))
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or(error!(crate::errors::RewardError::ArithmeticOverflow))?;
+        Ok(withholding_amount.min(self.deposit_debt))
+    }
 }
 #[account]
+#[derive(InitSpace)]
 pub struct AdminAccount {
+    pub version: u8,
     pub admin_pubkey: Pubkey,
     pub admin_candidate_pubkey: Pubkey,
-    pub paused: bool,
+    // PAUSE_* subsystems currently paused
+    pub pause_flags: u8,
     pub admin_update_requested: bool,
     pub valid_mint: Pubkey,
-    pub mint_authorities: Vec<Pubkey>
-}
\ No newline at end of file
+    // token-group mint of the official nfnode collection, default disables the check
+    pub collection: Pubkey,
+    // how often owners, hosts and manufacturers can claim, zeroed means calendar UTC days
+    pub claim_period_mode: ClaimPeriodMode,
+    pub claim_period_seconds: i64,
+    pub claim_period_offset: i64,
+    // when withdrawals were paused, 0 while they run
+    pub paused_at: i64,
+    // how long a pause can last before holders can withdraw their deposits anyway, 0 uses the default
+    pub emergency_withdrawal_grace_period: i64,
+    // operator defined code explaining the current pause
+    pub pause_reason: u16,
+    // the pause lifts itself at this timestamp, 0 never expires
+    pub pause_expires_at: i64,
+    pub reserved: [u8; 54],
+}
+impl AdminAccount {
+    // version 2 moved mint authorities to their own MintAuthorityEntry accounts,
+    // version 3 added the pause timestamp and the emergency withdrawal grace period,
+    // version 4 replaced the paused bool with per subsystem pause flags
+    pub const VERSION: u8 = 4;
+
+    pub fn active_pause_flags(&self, current_timestamp: i64) -> u8 {
+        if self.pause_expires_at != 0 && current_timestamp >= self.pause_expires_at {
+            0
+        } else {
+            self.pause_flags
+        }
+    }
+
+    pub fn is_paused(&self, pause_flag: u8, current_timestamp: i64) -> bool {
+        self.active_pause_flags(current_timestamp) & pause_flag != 0
+    }
+
+    pub fn set_pause(
+        &mut self,
+        pause_flags: u8,
+        pause_reason: u16,
+        pause_expires_at: i64,
+        current_timestamp: i64
+    ) {
+        // the emergency withdrawal grace period runs from when withdrawals were paused
+        let withdrawals_paused = self.is_paused(PAUSE_WITHDRAWALS, current_timestamp);
+        if pause_flags & PAUSE_WITHDRAWALS == 0 {
+            self.paused_at = 0;
+        } else if !withdrawals_paused || self.paused_at == 0 {
+            self.paused_at = current_timestamp;
+        }
+        self.pause_flags = pause_flags;
+        self.pause_reason = if pause_flags == 0 { 0 } else { pause_reason };
+        self.pause_expires_at = if pause_flags == 0 { 0 } else { pause_expires_at };
+    }
+
+    pub fn emergency_withdrawal_grace_period(&self) -> i64 {
+        if self.emergency_withdrawal_grace_period == 0 {
+            DEFAULT_EMERGENCY_WITHDRAWAL_GRACE_PERIOD
+        } else {
+            self.emergency_withdrawal_grace_period
+        }
+    }
+
+    // deposits are never held hostage by a pause longer than the grace period
+    pub fn emergency_withdrawal_open(&self, current_timestamp: i64) -> bool {
+        self.is_paused(PAUSE_WITHDRAWALS, current_timestamp) &&
+            self.paused_at != 0 &&
+            current_timestamp.saturating_sub(self.paused_at) >=
+                self.emergency_withdrawal_grace_period()
+    }
+
+    pub fn claim_period(&self) -> i64 {
+        if self.claim_period_seconds == 0 { DEFAULT_CLAIM_PERIOD } else { self.claim_period_seconds }
+    }
+
+    // true once a new claim period started since the last claim, never claimed is always true
+    pub fn claim_period_elapsed(&self, last_claimed_timestamp: i64, current_timestamp: i64) -> bool {
+        if last_claimed_timestamp == 0 {
+            return true;
+        }
+        let claim_period = self.claim_period();
+        match self.claim_period_mode {
+            ClaimPeriodMode::CalendarAligned =>
+                (current_timestamp - self.claim_period_offset).div_euclid(claim_period) >
+                    (last_claimed_timestamp - self.claim_period_offset).div_euclid(claim_period),
+            ClaimPeriodMode::Rolling => current_timestamp - last_claimed_timestamp >= claim_period,
+        }
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
+pub enum ClaimPeriodMode {
+    // periods start every claim_period_seconds, shifted by claim_period_offset from the unix epoch
+    CalendarAligned,
+    // a new claim is allowed claim_period_seconds after the previous one
+    Rolling,
+}
+// one per authorized nft mint authority, closed when the authority is removed
+#[account]
+#[derive(InitSpace)]
+pub struct MintAuthorityEntry {
+    pub version: u8,
+    pub authority: Pubkey,
+    // window in which initialize_nfnode accepts the authority's nfts, registration_closes_at = 0 never
+    // closes. Token-2022 mints record no creation time, so the window bounds registration, not minting
+    pub registration_opens_at: i64,
+    pub registration_closes_at: i64,
+    // NfNodeType flags the authority may mint
+    pub allowed_nfnode_types: u8,
+    pub reserved: [u8; 15],
+}
+impl MintAuthorityEntry {
+    // version 2 added the grant fields in the reserved bytes
+    pub const VERSION: u8 = 2;
+
+    pub fn set_grant(
+        &mut self,
+        registration_opens_at: i64,
+        registration_closes_at: i64,
+        allowed_nfnode_types: &[NfNodeType]
+    ) -> Result<()> {
+        require!(
+            registration_closes_at == 0 || registration_closes_at > registration_opens_at,
+            crate::errors::RewardError::InvalidMintAuthorityGrant
+        );
+        require!(!allowed_nfnode_types.is_empty(), crate::errors::RewardError::InvalidMintAuthorityGrant);
+        self.version = Self::VERSION;
+        self.registration_opens_at = registration_opens_at;
+        self.registration_closes_at = registration_closes_at;
+        self.allowed_nfnode_types = NfNodeType::flags(allowed_nfnode_types);
+        Ok(())
+    }
+    // version 1 entries predate grants and stay unrestricted until the admin sets one
+    pub fn check_registration_grant(&self, nfnode_type: &NfNodeType, timestamp: i64) -> Result<()> {
+        if self.version < 2 {
+            return Ok(());
+        }
+        require!(
+            timestamp >= self.registration_opens_at &&
+                (self.registration_closes_at == 0 || timestamp < self.registration_closes_at),
+            crate::errors::RewardError::MintAuthorityGrantExpired
+        );
+        require!(
+            self.allowed_nfnode_types & nfnode_type.flag() != 0,
+            crate::errors::RewardError::NfNodeTypeNotAllowed
+        );
+        Ok(())
+    }
+}
+#[account]
+#[derive(InitSpace)]
+pub struct Host {
+    pub version: u8,
+    pub host: Pubkey,
+    pub last_claimed_nonce: u64,
+    pub last_claimed_timestamp: i64,
+    pub total_rewards_claimed: u64,
+    // unpaid remainder of batches the pool could not fully cover, paid with the next batch
+    pub owed_amount: u64,
+    pub reserved: [u8; 24],
+}
+impl Host {
+    pub const VERSION: u8 = 1;
+}
+// Token2022 is the first variant so nfnodes registered before identity backends decode as Token2022
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
+pub enum NfNodeIdentity {
+    Token2022,
+    CoreAsset,
+    CompressedNft,
+}
+// official Metaplex Core collection or Bubblegum merkle tree nfnode assets may come from
+#[account]
+#[derive(InitSpace)]
+pub struct IdentitySource {
+    pub version: u8,
+    pub identity: NfNodeIdentity,
+    pub source: Pubkey,
+    pub reserved: [u8; 32],
+}
+impl IdentitySource {
+    pub const VERSION: u8 = 1;
+}
+// leaf of a Bubblegum compressed nft, the merkle proof nodes go in remaining accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedNftProof {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub delegate: Pubkey,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ClaimRole {
+    Owner,
+    Host,
+    Manufacturer,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct PayoutRoute {
+    // wallet that configured the route, the route is ignored once the role changes hands
+    pub beneficiary: Pubkey,
+    // wallet or token account that receives the rewards
+    pub destination: Pubkey,
+}
+#[account]
+#[derive(InitSpace)]
+pub struct PayoutConfig {
+    pub version: u8,
+    pub owner: PayoutRoute,
+    pub host: PayoutRoute,
+    pub manufacturer: PayoutRoute,
+    pub reserved: [u8; 32],
+}
+impl PayoutConfig {
+    pub const VERSION: u8 = 1;
+
+    pub fn route(&self, role: &ClaimRole) -> &PayoutRoute {
+        match role {
+            ClaimRole::Owner => &self.owner,
+            ClaimRole::Host => &self.host,
+            ClaimRole::Manufacturer => &self.manufacturer,
+        }
+    }
+    pub fn route_mut(&mut self, role: &ClaimRole) -> &mut PayoutRoute {
+        match role {
+            ClaimRole::Owner => &mut self.owner,
+            ClaimRole::Host => &mut self.host,
+            ClaimRole::Manufacturer => &mut self.manufacturer,
+        }
+    }
+    // payout_config is optional on claims, an uninitialized account means no routing
+    pub fn destination_for(
+        payout_config: &AccountInfo,
+        role: &ClaimRole,
+        beneficiary: &Pubkey
+    ) -> Result<Option<Pubkey>> {
+        if payout_config.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*payout_config.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let payout_config_data = payout_config.try_borrow_data()?;
+        let payout_config = PayoutConfig::try_deserialize(&mut &payout_config_data[..])?;
+        let route = payout_config.route(role);
+        if route.beneficiary != *beneficiary || route.destination == Pubkey::default() {
+            return Ok(None);
+        }
+        Ok(Some(route.destination))
+    }
+}
+#[account]
+#[derive(InitSpace)]
+pub struct AutoCompound {
+    pub version: u8,
+    // nft holder that opted in, the setting is ignored once the nft changes hands
+    pub owner: Pubkey,
+    pub compound_bps: u16,
+    // keep compounding past the deposit requirement as additional stake
+    pub stake_beyond_requirement: bool,
+    // first opt-in on the nfnode, starts the coverage period, settings from before it read as 0
+    pub enabled_at: i64,
+    pub reserved: [u8; 24],
+}
+impl AutoCompound {
+    pub const VERSION: u8 = 1;
+
+    // auto_compound is optional on claims, an uninitialized or disabled account means no compounding
+    pub fn load_for(auto_compound: &AccountInfo, owner: &Pubkey) -> Result<Option<AutoCompound>> {
+        if auto_compound.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*auto_compound.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let auto_compound_data = auto_compound.try_borrow_data()?;
+        let auto_compound = AutoCompound::try_deserialize(&mut &auto_compound_data[..])?;
+        if auto_compound.owner != *owner || auto_compound.compound_bps == 0 {
+            return Ok(None);
+        }
+        Ok(Some(auto_compound))
+    }
+    // an nfnode without a covered deposit can claim while compounding fills it
+    pub fn waives_deposit(&self, now: i64) -> bool {
+        self.compound_bps >= MIN_DEPOSIT_COMPOUND_BPS &&
+            self.enabled_at != 0 &&
+            now < self.enabled_at.saturating_add(AUTO_COMPOUND_COVERAGE_PERIOD)
+    }
+    pub fn compound_amount(
+        &self,
+        reward_amount: u64,
+        deposit_amount: u64,
+        deposit_requirement: u64
+    ) -> Result<u64> {
+        let compound_amount = (reward_amount as u128)
+            .checked_mul(self.compound_bps as u128)
+            .and_then(|amount| amount.checked_div(10000))
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or(error!(crate::errors::RewardError::ArithmeticOverflow))?;
+        if self.stake_beyond_requirement {
+            return Ok(compound_amount);
+        }
+        Ok(compound_amount.min(deposit_requirement.saturating_sub(deposit_amount)))
+    }
+}
+#[account]
+#[derive(InitSpace)]
+pub struct NfNodeLocation {
+    pub version: u8,
+    pub h3_cell: u64,
+    pub asserted_by: Pubkey,
+    pub assertion_timestamp: i64,
+    pub assertion_fee: u64,
+    pub reserved: [u8; 32],
+}
+impl NfNodeLocation {
+    pub const VERSION: u8 = 1;
+
+    // reward multiplier of the nfnode region, nfnodes without a location or in a region
+    // without a multiplier keep the full reward
+    pub fn reward_multiplier_bps(
+        nfnode_location: &AccountInfo,
+        h3_region: Option<&AccountInfo>
+    ) -> Result<u16> {
+        if nfnode_location.data_is_empty() {
+            return Ok(10000);
+        }
+        require_keys_eq!(*nfnode_location.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let nfnode_location_data = nfnode_location.try_borrow_data()?;
+        let nfnode_location = NfNodeLocation::try_deserialize(&mut &nfnode_location_data[..])?;
+        let region_cell = crate::location::h3_parent(nfnode_location.h3_cell, H3_REGION_RESOLUTION)?;
+        let (derived_h3_region, _bump_seed) = Pubkey::find_program_address(
+            &[b"h3_region", &region_cell.to_le_bytes()],
+            &crate::ID
+        );
+        let h3_region = h3_region.ok_or(crate::errors::RewardError::InvalidH3Region)?;
+        require_keys_eq!(derived_h3_region, h3_region.key(), crate::errors::RewardError::InvalidH3Region);
+        if h3_region.data_is_empty() {
+            return Ok(10000);
+        }
+        require_keys_eq!(*h3_region.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let h3_region_data = h3_region.try_borrow_data()?;
+        let h3_region = H3Region::try_deserialize(&mut &h3_region_data[..])?;
+        Ok(h3_region.multiplier_bps)
+    }
+}
+// admin configured reward multiplier of every nfnode located inside an H3 cell at H3_REGION_RESOLUTION
+#[account]
+#[derive(InitSpace)]
+pub struct H3Region {
+    pub version: u8,
+    pub region_cell: u64,
+    pub multiplier_bps: u16,
+    pub reserved: [u8; 32],
+}
+impl H3Region {
+    pub const VERSION: u8 = 1;
+}
+// grows a legacy account to its current layout, topping up rent from the payer
+pub fn realloc_for_migration<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    new_len: usize
+) -> Result<()> {
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(new_len);
+    let lamports = account.lamports();
+    if rent_exempt_lamports > lamports {
+        system_program::transfer(
+            CpiContext::new(system_program_info.clone(), system_program::Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            }),
+            rent_exempt_lamports - lamports
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+// a wallet barred from receiving rewards, closed when removed from the blacklist
+#[account]
+#[derive(InitSpace)]
+pub struct BlacklistEntry {
+    pub version: u8,
+    pub wallet: Pubkey,
+    pub reason: u16,
+    pub blacklisted_at: i64,
+    pub appealed: bool,
+    pub reserved: [u8; 32],
+}
+impl BlacklistEntry {
+    pub const VERSION: u8 = 1;
+
+    // blacklist_entry is derived from the beneficiary on claims, it only exists for blacklisted wallets
+    pub fn require_not_blacklisted(blacklist_entry: &AccountInfo) -> Result<()> {
+        if blacklist_entry.data_is_empty() {
+            return Ok(());
+        }
+        require_keys_eq!(*blacklist_entry.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        BlacklistEntry::try_deserialize(&mut &blacklist_entry.try_borrow_data()?[..])?;
+        err!(crate::errors::RewardError::WalletBlacklisted)
+    }
+    // a payout routed away from the beneficiary also checks the entry derived from the wallet it reaches
+    pub fn require_wallet_not_blacklisted(
+        wallet: &Pubkey,
+        blacklist_entry: Option<&AccountInfo>
+    ) -> Result<()> {
+        let blacklist_entry = blacklist_entry.ok_or(
+            crate::errors::RewardError::InvalidBlacklistEntry
+        )?;
+        let (derived_blacklist_entry, _bump_seed) = Pubkey::find_program_address(
+            &[b"blacklist", wallet.as_ref()],
+            &crate::ID
+        );
+        require_keys_eq!(
+            blacklist_entry.key(),
+            derived_blacklist_entry,
+            crate::errors::RewardError::InvalidBlacklistEntry
+        );
+        BlacklistEntry::require_not_blacklisted(blacklist_entry)
+    }
+}
+// tracks what flows in and out of the token_storage reward pool
+#[account]
+#[derive(InitSpace)]
+pub struct RewardPool {
+    pub version: u8,
+    pub total_funded: u64,
+    pub total_claimed: u64,
+    // rewards owed to claimers but not yet paid out
+    pub outstanding_liabilities: u64,
+    pub funder_count: u32,
+    // cut of every claim sent to the treasury and burned, in basis points
+    pub protocol_fee_bps: u16,
+    pub burn_bps: u16,
+    pub treasury_token_account: Pubkey,
+    pub total_protocol_fees: u64,
+    pub total_burned: u64,
+    // share of the token_storage balance held back from new claims to pay owed rewards
+    pub reserve_ratio_bps: u16,
+    pub reserved: [u8; 62],
+}
+impl RewardPool {
+    pub const VERSION: u8 = 1;
+
+    // (protocol fee, burn) taken from a claim before it is paid out
+    pub fn protocol_cut(&self, reward_amount: u64) -> Result<(u64, u64)> {
+        let cut = |bps: u16| {
+            (reward_amount as u128)
+                .checked_mul(bps as u128)
+                .and_then(|amount| amount.checked_div(10000))
+                .and_then(|amount| u64::try_from(amount).ok())
+                .ok_or(error!(crate::errors::RewardError::ArithmeticOverflow))
+        };
+        Ok((cut(self.protocol_fee_bps)?, cut(self.burn_bps)?))
+    }
+
+    // portion of the token_storage balance new claims may draw on
+    pub fn claimable_balance(&self, token_storage_balance: u64) -> Result<u64> {
+        let reserve = (token_storage_balance as u128)
+            .checked_mul(self.reserve_ratio_bps as u128)
+            .and_then(|amount| amount.checked_div(10000))
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        Ok(token_storage_balance - (reserve as u64))
+    }
+
+    // a pool short of its liabilities pays each claim the same share of what it can cover,
+    // claimable / (outstanding_liabilities + reward), the unpaid remainder is owed to the claimer
+    pub fn record_shortfall(
+        &mut self,
+        owed_amount: &mut u64,
+        reward_amount: u64,
+        token_storage_balance: u64
+    ) -> Result<u64> {
+        let claimable_balance = self.claimable_balance(token_storage_balance)?;
+        let demand = (self.outstanding_liabilities as u128) + (reward_amount as u128);
+        let paid_amount = if demand <= (claimable_balance as u128) {
+            reward_amount
+        } else {
+            (((reward_amount as u128) * (claimable_balance as u128)) / demand) as u64
+        };
+        let unpaid_amount = reward_amount - paid_amount;
+        if unpaid_amount > 0 {
+            *owed_amount = owed_amount
+                .checked_add(unpaid_amount)
+                .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+            self.outstanding_liabilities = self.outstanding_liabilities
+                .checked_add(unpaid_amount)
+                .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        }
+        Ok(paid_amount)
+    }
+
+    // credits tokens that reached token_storage to the pool and to the wallet they came from
+    pub fn record_funding(
+        &mut self,
+        funder_entry: &mut FunderEntry,
+        funder: Pubkey,
+        amount: u64,
+        now: i64
+    ) -> Result<()> {
+        if funder_entry.total_funded == 0 {
+            funder_entry.version = FunderEntry::VERSION;
+            funder_entry.funder = funder;
+            self.funder_count = self.funder_count
+                .checked_add(1)
+                .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        }
+        funder_entry.total_funded = funder_entry.total_funded
+            .checked_add(amount)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        funder_entry.last_funded_timestamp = now;
+        self.total_funded = self.total_funded
+            .checked_add(amount)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+// everything a wallet has put into the reward pool
+#[account]
+#[derive(InitSpace)]
+pub struct FunderEntry {
+    pub version: u8,
+    pub funder: Pubkey,
+    pub total_funded: u64,
+    pub last_funded_timestamp: i64,
+    pub reserved: [u8; 32],
+}
+impl FunderEntry {
+    pub const VERSION: u8 = 1;
+}
+// treasury deposit released into token_storage at a fixed rate by the drip crank
+#[account]
+#[derive(InitSpace)]
+pub struct RewardStream {
+    pub version: u8,
+    pub treasury: Pubkey,
+    pub total_amount: u64,
+    pub released_amount: u64,
+    pub rate_per_second: u64,
+    pub start_timestamp: i64,
+    pub last_drip_timestamp: i64,
+    pub reserved: [u8; 32],
+}
+impl RewardStream {
+    pub const VERSION: u8 = 1;
+
+    // accrued since the last drip, capped at what is left in the stream
+    pub fn releasable_amount(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.last_drip_timestamp).max(0) as u128;
+        let accrued = elapsed
+            .checked_mul(self.rate_per_second as u128)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        let remaining = self.total_amount
+            .checked_sub(self.released_amount)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        Ok(accrued.min(remaining as u128) as u64)
+    }
+}
+// delegated stake on an nfnode, delegators share the owner rewards in proportion to stake
+#[account]
+#[derive(InitSpace)]
+pub struct NfNodeStake {
+    pub version: u8,
+    pub nfnode_mint: Pubkey,
+    // share of the delegator rewards the owner keeps, in basis points
+    pub commission_bps: u16,
+    pub total_staked: u64,
+    // delegator rewards per staked token, scaled by STAKE_REWARD_PRECISION
+    pub reward_per_token: u128,
+    pub total_rewards_distributed: u64,
+    // commission increase waiting for STAKE_COMMISSION_DELAY, commission_effective_at = 0 when none
+    pub pending_commission_bps: u16,
+    pub commission_effective_at: i64,
+    // most stake the owner accepts on the nfnode, 0 closes it to new stake
+    pub stake_cap: u64,
+    // stake younger than MIN_STAKE_AGE, not part of total_staked yet
+    pub total_warming: u64,
+    pub reserved: [u8; 6],
+}
+impl NfNodeStake {
+    pub const VERSION: u8 = 1;
+
+    // set by the nfnode owner, the first setting opens the nfnode to delegated stake
+    pub fn configure(&mut self, nfnode_mint: Pubkey, commission_bps: u16, stake_cap: u64, now: i64) {
+        if self.version == 0 {
+            self.version = Self::VERSION;
+            self.nfnode_mint = nfnode_mint;
+            self.commission_bps = commission_bps;
+        } else {
+            self.apply_pending_commission(now);
+            // lowering the commission applies right away and drops a scheduled increase
+            if commission_bps <= self.commission_bps {
+                self.commission_bps = commission_bps;
+                self.commission_effective_at = 0;
+            } else {
+                self.pending_commission_bps = commission_bps;
+                self.commission_effective_at = now.saturating_add(STAKE_COMMISSION_DELAY);
+            }
+        }
+        self.stake_cap = stake_cap;
+    }
+    pub fn apply_pending_commission(&mut self, now: i64) {
+        if self.commission_effective_at != 0 && now >= self.commission_effective_at {
+            self.commission_bps = self.pending_commission_bps;
+            self.commission_effective_at = 0;
+        }
+    }
+    pub fn require_stake_capacity(&self, amount: u64) -> Result<()> {
+        let total = (self.total_staked as u128) + (self.total_warming as u128) + (amount as u128);
+        require!(
+            self.version != 0 && total <= (self.stake_cap as u128),
+            crate::errors::RewardError::StakeCapExceeded
+        );
+        Ok(())
+    }
+
+    // (delegator share, owner commission) of an owner reward, the deposit counts as the owner's stake
+    pub fn delegator_share(&self, reward_amount: u64, deposit_amount: u64) -> Result<(u64, u64)> {
+        if self.total_staked == 0 {
+            return Ok((0, 0));
+        }
+        let total_stake = (self.total_staked as u128) + (deposit_amount as u128);
+        let gross = (reward_amount as u128)
+            .checked_mul(self.total_staked as u128)
+            .and_then(|amount| amount.checked_div(total_stake))
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        let commission = gross
+            .checked_mul(self.commission_bps as u128)
+            .and_then(|amount| amount.checked_div(10000))
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        Ok(((gross - commission) as u64, commission as u64))
+    }
+
+    pub fn distribute(&mut self, delegator_amount: u64) -> Result<()> {
+        let increment = (delegator_amount as u128)
+            .checked_mul(STAKE_REWARD_PRECISION)
+            .and_then(|amount| amount.checked_div(self.total_staked as u128))
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        self.reward_per_token = self.reward_per_token
+            .checked_add(increment)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        self.total_rewards_distributed = self.total_rewards_distributed
+            .checked_add(delegator_amount)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+// a delegator's stake on one nfnode
+#[account]
+#[derive(InitSpace)]
+pub struct StakePosition {
+    pub version: u8,
+    pub delegator: Pubkey,
+    pub nfnode_mint: Pubkey,
+    pub staked_amount: u64,
+    // NfNodeStake.reward_per_token at the last settlement
+    pub reward_per_token_paid: u128,
+    pub pending_rewards: u64,
+    // unstaked but still cooling down, earns nothing
+    pub unstaking_amount: u64,
+    pub unstake_requested_at: i64,
+    // stake earning nothing until MIN_STAKE_AGE after warming_since, a top-up restarts the warm-up
+    pub warming_amount: u64,
+    pub warming_since: i64,
+    pub reserved: [u8; 16],
+}
+impl StakePosition {
+    pub const VERSION: u8 = 1;
+
+    // moves rewards accrued since the last settlement into pending_rewards
+    pub fn settle(&mut self, nfnode_stake: &NfNodeStake) -> Result<()> {
+        let earned = (self.staked_amount as u128)
+            .checked_mul(nfnode_stake.reward_per_token - self.reward_per_token_paid)
+            .map(|amount| amount / STAKE_REWARD_PRECISION)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        self.pending_rewards = self.pending_rewards
+            .checked_add(earned as u64)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        self.reward_per_token_paid = nfnode_stake.reward_per_token;
+        Ok(())
+    }
+
+    // moves warmed up stake into the earning stake, settle first so it earns nothing from before
+    pub fn activate(&mut self, nfnode_stake: &mut NfNodeStake, now: i64) -> Result<bool> {
+        if self.warming_amount == 0 || now < self.warming_since.saturating_add(MIN_STAKE_AGE) {
+            return Ok(false);
+        }
+        self.staked_amount = self.staked_amount
+            .checked_add(self.warming_amount)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        nfnode_stake.total_staked = nfnode_stake.total_staked
+            .checked_add(self.warming_amount)
+            .ok_or(crate::errors::RewardError::ArithmeticOverflow)?;
+        nfnode_stake.total_warming -= self.warming_amount;
+        self.warming_amount = 0;
+        Ok(true)
+    }
+
+    pub fn unstake_cooldown_elapsed(&self, now: i64) -> bool {
+        now >= self.unstake_requested_at.saturating_add(UNSTAKE_COOLDOWN)
+    }
+}
@@ -7,11 +7,13 @@ export async function addMintAuthority(
   program: Program<RewardSystem>,
   adminKeypair: Keypair,
   newMintAuthority: PublicKey,
-  adminAccountPDA: PublicKey
+  registrationOpensAt: anchor.BN = new anchor.BN(0),
+  registrationClosesAt: anchor.BN = new anchor.BN(0),
+  allowedNfnodeTypes: any[] = [{ don: {} }, { byod: {} }, { wayruHotspot: {} }]
 ) {
   try {
     await program.methods
-      .addMintAuthority(newMintAuthority)
+      .addMintAuthority(newMintAuthority, registrationOpensAt, registrationClosesAt, allowedNfnodeTypes)
       .accounts({
         user: adminKeypair.publicKey,
       })
@@ -28,12 +30,15 @@ export async function addMintAuthority(
   let times = 0;
   while (!updated && times < 10) {
     try {
-      const adminAccountState = await program.account.adminAccount.fetch(
-        adminAccountPDA,
+      const [mintAuthorityEntryPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint_authority"), newMintAuthority.toBuffer()],
+        program.programId
+      );
+      const mintAuthorityEntryState = await program.account.mintAuthorityEntry.fetch(
+        mintAuthorityEntryPDA,
         "finalized"
       );
-      const mintAuthorities = adminAccountState.mintAuthorities.map((mintAuthority) => mintAuthority.toBase58());
-      updated = mintAuthorities.includes(newMintAuthority.toBase58());
+      updated = mintAuthorityEntryState.authority.equals(newMintAuthority);
     } catch (error) {
       await new Promise((resolve) => setTimeout(resolve, 10000));
       times++;
@@ -0,0 +1,230 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+// helpers for Metaplex Core NfNodes, compressed NfNodes also pass the merkle tree, the
+// compression program, the proof nodes and a compressed nft proof
+export function getIdentitySourcePDA(program: Program<RewardSystem>, source: PublicKey) {
+  const [identitySourcePDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("identity_source"), source.toBuffer()],
+    program.programId
+  );
+  return identitySourcePDA;
+}
+
+export async function assetDepositTokens(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  mint: PublicKey,
+  asset: PublicKey,
+  identitySource: PublicKey,
+  amount: anchor.BN,
+  lockupTier: any = { days30: {} }
+) {
+  await program.methods
+    .assetDepositTokens(amount, lockupTier, null)
+    .accounts({
+      user: userKeypair.publicKey,
+      tokenMint: mint,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function assetWithdrawTokens(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  mint: PublicKey,
+  asset: PublicKey,
+  identitySource: PublicKey,
+  amount: anchor.BN
+) {
+  await program.methods
+    .assetWithdrawTokens(amount, null)
+    .accounts({
+      user: userKeypair.publicKey,
+      tokenMint: mint,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function assetSetPayoutDestination(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  asset: PublicKey,
+  role: { owner: {} } | { host: {} } | { manufacturer: {} },
+  destination: PublicKey,
+  identitySource: PublicKey | null = null
+) {
+  await program.methods
+    .assetSetPayoutDestination(role, destination, null)
+    .accounts({
+      user: userKeypair.publicKey,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function assetSetAutoCompound(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  asset: PublicKey,
+  identitySource: PublicKey,
+  compoundBps: number,
+  stakeBeyondRequirement: boolean
+) {
+  await program.methods
+    .assetSetAutoCompound(compoundBps, stakeBeyondRequirement, null)
+    .accounts({
+      user: userKeypair.publicKey,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function assetSetStakeCommission(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  asset: PublicKey,
+  identitySource: PublicKey,
+  commissionBps: number,
+  stakeCap: anchor.BN
+) {
+  await program.methods
+    .assetSetStakeCommission(commissionBps, stakeCap, null)
+    .accounts({
+      user: userKeypair.publicKey,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function initializeAssetNfnode(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  userKeypair: Keypair,
+  host: PublicKey,
+  manufacturer: PublicKey,
+  mint: PublicKey,
+  asset: PublicKey,
+  identitySource: PublicKey,
+  nfnodeType: { don: {} } | { byod: {} } | { wayruHotspot: {} }
+) {
+  await program.methods
+    .initializeAssetNfnode(new anchor.BN(0), nfnodeType, null)
+    .accounts({
+      userAdmin: adminKeypair.publicKey,
+      user: userKeypair.publicKey,
+      host,
+      manufacturer,
+      tokenMint: mint,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([adminKeypair, userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+// identitySource is null for the depositor of a deposit that does not carry with the asset
+export async function assetSetDepositCarry(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  asset: PublicKey,
+  identitySource: PublicKey | null,
+  carriesWithNft: boolean
+) {
+  await program.methods
+    .assetSetDepositCarry(carriesWithNft, null)
+    .accounts({
+      user: userKeypair.publicKey,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function assetRequestWithdrawal(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  asset: PublicKey,
+  identitySource: PublicKey | null
+) {
+  await program.methods
+    .assetRequestWithdrawal(null)
+    .accounts({
+      user: userKeypair.publicKey,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function assetCancelWithdrawal(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  asset: PublicKey,
+  identitySource: PublicKey | null
+) {
+  await program.methods
+    .assetCancelWithdrawal(null)
+    .accounts({
+      user: userKeypair.publicKey,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function assetCompleteWithdrawal(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  mint: PublicKey,
+  asset: PublicKey,
+  identitySource: PublicKey | null
+) {
+  await program.methods
+    .assetCompleteWithdrawal(null)
+    .accounts({
+      user: userKeypair.publicKey,
+      tokenMint: mint,
+      asset,
+      identitySource,
+      merkleTree: null,
+      compressionProgram: null,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -0,0 +1,45 @@
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function blacklistWallet(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  wallet: PublicKey,
+  reason: number
+) {
+  await program.methods
+    .blacklistWallet(wallet, reason)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function removeBlacklistedWallet(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  wallet: PublicKey
+) {
+  await program.methods
+    .removeBlacklistedWallet(wallet)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function appealBlacklist(
+  program: Program<RewardSystem>,
+  walletKeypair: Keypair
+) {
+  await program.methods
+    .appealBlacklist()
+    .accounts({
+      user: walletKeypair.publicKey,
+    })
+    .signers([walletKeypair])
+    .rpc({ commitment: "confirmed" });
+}
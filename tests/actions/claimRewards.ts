@@ -3,6 +3,11 @@ import { Program } from "@coral-xyz/anchor";
 import { RewardSystem } from "../../target/types/reward_system";
 import { PublicKey, Keypair, Connection } from "@solana/web3.js";
 import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import {
+    getH3RegionPDA,
+    getMintAuthorityEntryPDA,
+    getRecipientBlacklistEntryPDA,
+} from "../utils";
 
 export async function ownerClaimRewards(
     program: Program<RewardSystem>,
@@ -13,7 +18,9 @@ export async function ownerClaimRewards(
     nftMint: PublicKey,
     userNFTTokenAccount: PublicKey,
     rewardAmount: anchor.BN,
-    nonce: anchor.BN
+    nonce: anchor.BN,
+    recipientTokenAccount: PublicKey | null = null,
+    depositStorageAccount: PublicKey | null = null
 ) {
     const ix = await program.methods
         .ownerClaimRewards(rewardAmount, nonce)
@@ -22,8 +29,15 @@ export async function ownerClaimRewards(
             user: userKeypair.publicKey,
             tokenMint: mint,
             nftMintAddress: nftMint,
+            mintAuthorityEntry: await getMintAuthorityEntryPDA(program, nftMint),
+            h3Region: await getH3RegionPDA(program, nftMint),
             tokenProgram2022: TOKEN_2022_PROGRAM_ID,
             userNftTokenAccount: userNFTTokenAccount,
+            recipientTokenAccount,
+            recipientBlacklistEntry: await getRecipientBlacklistEntryPDA(program, recipientTokenAccount),
+            treasuryTokenAccount: null,
+            stakeVaultAccount: null,
+            depositStorageAccount,
         })
         .instruction();
 
@@ -61,15 +75,27 @@ export async function othersClaimRewards(
     mint: PublicKey,
     nftMint: PublicKey,
     rewardAmount: anchor.BN,
-    nonce: anchor.BN
+    nonce: anchor.BN,
+    recipientTokenAccount: PublicKey | null = null,
+    role: "host" | "manufacturer" | null = null
 ) {
-    const ix = await program.methods
-        .othersClaimRewards(rewardAmount, nonce)
+    // without a role the program resolves it from the signer, host first
+    const method = role === "host"
+        ? program.methods.hostClaimRewards(rewardAmount, nonce)
+        : role === "manufacturer"
+            ? program.methods.manufacturerClaimRewards(rewardAmount, nonce)
+            : program.methods.othersClaimRewards(rewardAmount, nonce);
+    const ix = await method
         .accounts({
             userAdmin: adminKeypair.publicKey,
             user: userKeypair.publicKey,
             tokenMint: mint,
             nftMintAddress: nftMint,
+            mintAuthorityEntry: await getMintAuthorityEntryPDA(program, nftMint),
+            h3Region: await getH3RegionPDA(program, nftMint),
+            recipientTokenAccount,
+            recipientBlacklistEntry: await getRecipientBlacklistEntryPDA(program, recipientTokenAccount),
+            treasuryTokenAccount: null,
         })
         .instruction();
 
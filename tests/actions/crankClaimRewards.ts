@@ -0,0 +1,74 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { PublicKey, Keypair, Connection } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import {
+    getH3RegionPDA,
+    getMintAuthorityEntryPDA,
+    getRecipientBlacklistEntryPDA,
+} from "../utils";
+
+export async function crankClaimRewards(
+    program: Program<RewardSystem>,
+    provider: anchor.AnchorProvider,
+    adminKeypair: Keypair,
+    crankerKeypair: Keypair,
+    beneficiary: PublicKey,
+    role: { owner: {} } | { host: {} } | { manufacturer: {} },
+    mint: PublicKey,
+    nftMint: PublicKey,
+    beneficiaryNFTTokenAccount: PublicKey | null,
+    rewardAmount: anchor.BN,
+    nonce: anchor.BN,
+    crankFee: anchor.BN,
+    crankerTokenAccount: PublicKey | null = null,
+    recipientTokenAccount: PublicKey | null = null,
+    depositStorageAccount: PublicKey | null = null
+) {
+    const ix = await program.methods
+        .crankClaimRewards(role, rewardAmount, nonce, crankFee)
+        .accounts({
+            userAdmin: adminKeypair.publicKey,
+            cranker: crankerKeypair.publicKey,
+            beneficiary,
+            tokenMint: mint,
+            nftMintAddress: nftMint,
+            mintAuthorityEntry: await getMintAuthorityEntryPDA(program, nftMint),
+            h3Region: await getH3RegionPDA(program, nftMint),
+            beneficiaryNftTokenAccount: beneficiaryNFTTokenAccount,
+            recipientTokenAccount,
+            recipientBlacklistEntry: await getRecipientBlacklistEntryPDA(program, recipientTokenAccount),
+            treasuryTokenAccount: null,
+            stakeVaultAccount: null,
+            crankerTokenAccount,
+            depositStorageAccount,
+            tokenProgram2022: TOKEN_2022_PROGRAM_ID,
+        })
+        .instruction();
+
+    let tx = new anchor.web3.Transaction();
+    tx.add(ix);
+    tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+    tx.feePayer = crankerKeypair.publicKey;
+    tx.partialSign(adminKeypair);
+
+    const serializedTx = tx.serialize({
+        requireAllSignatures: false,
+        verifySignatures: false,
+    });
+
+    const txBase64 = serializedTx.toString("base64");
+    const recoveredTx = anchor.web3.Transaction.from(Buffer.from(txBase64, "base64"));
+    recoveredTx.partialSign(crankerKeypair);
+
+    const connection = new Connection(process.env.SOLANA_API_URL);
+    const serializedTxFinal = recoveredTx.serialize({
+        requireAllSignatures: true,
+        verifySignatures: true,
+    });
+
+    const txId = await anchor.web3.sendAndConfirmRawTransaction(connection, serializedTxFinal, { commitment: 'confirmed' });
+    console.log("Rewards Cranked Successfully");
+    console.log("Transaction ID:", txId);
+}
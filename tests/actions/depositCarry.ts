@@ -0,0 +1,21 @@
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function setDepositCarry(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  nftMint: PublicKey,
+  userNFTTokenAccount: PublicKey | null, // null for a depositor who sold the nft
+  carriesWithNft: boolean
+) {
+  await program.methods
+    .setDepositCarry(carriesWithNft)
+    .accounts({
+      user: userKeypair.publicKey,
+      nftMintAddress: nftMint,
+      userNftTokenAccount: userNFTTokenAccount,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -3,20 +3,24 @@ import { Program } from "@coral-xyz/anchor";
 import { RewardSystem } from "../../target/types/reward_system";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { getMintAuthorityEntryPDA } from "../utils";
 
 export async function depositTokens(
     program: Program<RewardSystem>,
     userKeypair: Keypair,
     mint: PublicKey,
     nftMint: PublicKey,
-    userNFTTokenAccount: PublicKey
+    userNFTTokenAccount: PublicKey,
+    amount: anchor.BN = new anchor.BN(5000000000),
+    lockupTier: { days30: {} } | { days90: {} } | { days180: {} } | { days365: {} } = { days30: {} }
 ) {
     await program.methods
-        .depositTokens()
+        .depositTokens(amount, lockupTier)
         .accounts({
             user: userKeypair.publicKey,
             tokenMint: mint,
             nftMintAddress: nftMint,
+            mintAuthorityEntry: await getMintAuthorityEntryPDA(program, nftMint),
             tokenProgram2022: TOKEN_2022_PROGRAM_ID,
             userNftTokenAccount: userNFTTokenAccount
         })
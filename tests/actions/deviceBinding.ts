@@ -0,0 +1,65 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Ed25519Program, Keypair, PublicKey } from "@solana/web3.js";
+
+export async function bindDevice(
+  program: Program<RewardSystem>,
+  manufacturerKeypair: Keypair,
+  nftMint: PublicKey,
+  devicePubkey: PublicKey
+) {
+  await program.methods
+    .bindDevice(devicePubkey)
+    .accounts({
+      user: manufacturerKeypair.publicKey,
+      nftMintAddress: nftMint,
+    })
+    .signers([manufacturerKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function rebindDevice(
+  program: Program<RewardSystem>,
+  signerKeypair: Keypair,
+  nftMint: PublicKey,
+  devicePubkey: PublicKey
+) {
+  await program.methods
+    .rebindDevice(devicePubkey)
+    .accounts({
+      user: signerKeypair.publicKey,
+      nftMintAddress: nftMint,
+    })
+    .signers([signerKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function submitHeartbeat(
+  program: Program<RewardSystem>,
+  payerKeypair: Keypair,
+  deviceKeypair: Keypair,
+  nftMint: PublicKey,
+  timestamp: anchor.BN
+) {
+  // the device signs prefix || nfnode asset key || timestamp (i64 little endian)
+  const message = Buffer.concat([
+    Buffer.from("wayru-nfnode-heartbeat"),
+    nftMint.toBuffer(),
+    timestamp.toArrayLike(Buffer, "le", 8),
+  ]);
+  const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: deviceKeypair.secretKey,
+    message,
+  });
+  await program.methods
+    .submitHeartbeat(timestamp)
+    .accounts({
+      payer: payerKeypair.publicKey,
+      nftMintAddress: nftMint,
+      instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+    })
+    .preInstructions([ed25519Ix])
+    .signers([payerKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -0,0 +1,89 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { PublicKey, Keypair, Connection } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import {
+    getH3RegionPDA,
+    getMintAuthorityEntryPDA,
+    getRecipientBlacklistEntryPDA,
+} from "../utils";
+
+export async function hostClaimAll(
+    program: Program<RewardSystem>,
+    provider: anchor.AnchorProvider,
+    adminKeypair: Keypair,
+    hostKeypair: Keypair,
+    mint: PublicKey,
+    nftMints: PublicKey[],
+    rewardAmounts: anchor.BN[],
+    nonce: anchor.BN,
+    recipientTokenAccount: PublicKey | null = null
+) {
+    const remainingAccounts = (await Promise.all(nftMints.map(async (nftMint) => {
+        const [nfnodeEntryPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("nfnode_entry"), nftMint.toBuffer()],
+            program.programId
+        );
+        const [payoutConfigPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("payout_config"), nftMint.toBuffer()],
+            program.programId
+        );
+        const [nfnodeLocationPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("nfnode_location"), nftMint.toBuffer()],
+            program.programId
+        );
+        const nfnodeAccounts = [
+            { pubkey: nftMint, isWritable: false, isSigner: false },
+            { pubkey: nfnodeEntryPDA, isWritable: true, isSigner: false },
+            { pubkey: payoutConfigPDA, isWritable: false, isSigner: false },
+            { pubkey: nfnodeLocationPDA, isWritable: false, isSigner: false },
+        ];
+        // the region only follows once a location is asserted
+        const h3RegionPDA = await getH3RegionPDA(program, nftMint);
+        if (h3RegionPDA !== null) {
+            nfnodeAccounts.push({ pubkey: h3RegionPDA, isWritable: false, isSigner: false });
+        }
+        return nfnodeAccounts;
+    }))).flat();
+    const ix = await program.methods
+        .hostClaimAll(rewardAmounts, nonce)
+        .accounts({
+            userAdmin: adminKeypair.publicKey,
+            user: hostKeypair.publicKey,
+            tokenMint: mint,
+            tokenProgram2022: TOKEN_2022_PROGRAM_ID,
+            recipientTokenAccount,
+            recipientBlacklistEntry: await getRecipientBlacklistEntryPDA(program, recipientTokenAccount),
+            treasuryTokenAccount: null,
+            // every nfnode of the batch comes from the same mint authority
+            mintAuthorityEntry: await getMintAuthorityEntryPDA(program, nftMints[0]),
+        })
+        .remainingAccounts(remainingAccounts)
+        .instruction();
+
+    let tx = new anchor.web3.Transaction();
+    tx.add(ix);
+    tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+    tx.feePayer = hostKeypair.publicKey;
+    tx.partialSign(adminKeypair);
+
+    const serializedTx = tx.serialize({
+        requireAllSignatures: false,
+        verifySignatures: false,
+    });
+
+    const txBase64 = serializedTx.toString("base64");
+    const recoveredTx = anchor.web3.Transaction.from(Buffer.from(txBase64, "base64"));
+    recoveredTx.partialSign(hostKeypair);
+
+    const connection = new Connection(process.env.SOLANA_API_URL);
+    const serializedTxFinal = recoveredTx.serialize({
+        requireAllSignatures: true,
+        verifySignatures: true,
+    });
+
+    const txId = await anchor.web3.sendAndConfirmRawTransaction(connection, serializedTxFinal, { commitment: 'confirmed' });
+    console.log("Host Rewards Claimed Successfully");
+    console.log("Transaction ID:", txId);
+}
@@ -0,0 +1,33 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function addIdentitySource(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  source: PublicKey,
+  identity: any
+) {
+  await program.methods
+    .addIdentitySource(source, identity)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function removeIdentitySource(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  source: PublicKey
+) {
+  await program.methods
+    .removeIdentitySource(source)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
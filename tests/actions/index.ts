@@ -9,4 +9,27 @@ export * from "./unpauseProgram"
 export * from "./depositTokens"
 export * from "./withdrawTokens"
 export * from "./addMintAuthority"
-export * from "./removeMintAuthority"
\ No newline at end of file
+export * from "./removeMintAuthority"
+export * from "./initializeHost"
+export * from "./hostClaimAll"
+export * from "./setPayoutDestination"
+export * from "./crankClaimRewards"
+export * from "./setAutoCompound"
+export * from "./migrateNfnodeEntry"
+export * from "./setMintAuthorityGrant"
+export * from "./setNfnodeCollection"
+export * from "./identitySource"
+export * from "./deviceBinding"
+export * from "./location"
+export * from "./setClaimPeriod"
+export * from "./setEmergencyWithdrawalGracePeriod"
+export * from "./setPauseFlags"
+export * from "./nfnodeFreeze"
+export * from "./blacklist"
+export * from "./rewardPool"
+export * from "./rewardStream"
+export * from "./staking"
+export * from "./unbondDeposit"
+export * from "./depositCarry"
+export * from "./assetNfnode"
+export * from "./migrateHost"
\ No newline at end of file
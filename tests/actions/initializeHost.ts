@@ -0,0 +1,17 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair } from "@solana/web3.js";
+
+export async function initializeHost(
+  program: Program<RewardSystem>,
+  hostKeypair: Keypair
+) {
+  await program.methods
+    .initializeHost()
+    .accounts({
+      user: hostKeypair.publicKey,
+    })
+    .signers([hostKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -3,6 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { RewardSystem } from "../../target/types/reward_system";
 import { Keypair, PublicKey, Connection } from "@solana/web3.js";
 import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { getMintAuthorityEntryPDA } from "../utils";
 import { expect } from "chai";
 
 export async function initializeNfnode(
@@ -14,14 +15,16 @@ export async function initializeNfnode(
   userNFTTokenAccount: PublicKey,
   nfnodeEntryPDA: PublicKey,
   tokenMint: PublicKey,
-  nfnodeType: { don: {} } | { byod: {} } | { wayruHotspot: {} }
+  nfnodeType: { don: {} } | { byod: {} } | { wayruHotspot: {} },
+  debtWithholdingBps: number | null = null // financed deposit repaid from claims
 ) {
   await program.methods
-    .initializeNfnode(new anchor.BN(0), nfnodeType) // Pass the enum as an object
+    .initializeNfnode(new anchor.BN(0), nfnodeType, debtWithholdingBps) // Pass the enum as an object
     .accounts({
       userAdmin: adminKeypair.publicKey,
       user: userKeypair.publicKey,
       nftMintAddress: nftMint,
+      mintAuthorityEntry: await getMintAuthorityEntryPDA(program, nftMint),
       host: userKeypair.publicKey,
       manufacturer: user2Keypair.publicKey,
       tokenProgram2022: TOKEN_2022_PROGRAM_ID,
@@ -0,0 +1,37 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function assertLocation(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  mint: PublicKey,
+  nftMint: PublicKey,
+  h3Cell: anchor.BN
+) {
+  await program.methods
+    .assertLocation(h3Cell)
+    .accounts({
+      user: userKeypair.publicKey,
+      nftMintAddress: nftMint,
+      tokenMint: mint,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function setH3RegionMultiplier(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  regionCell: anchor.BN,
+  multiplierBps: number
+) {
+  await program.methods
+    .setH3RegionMultiplier(regionCell, multiplierBps)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -0,0 +1,19 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function migrateHost(
+  program: Program<RewardSystem>,
+  payerKeypair: Keypair,
+  host: PublicKey
+) {
+  await program.methods
+    .migrateHost()
+    .accounts({
+      payer: payerKeypair.publicKey,
+      host,
+    })
+    .signers([payerKeypair])
+    .rpc({ commitment: "confirmed" });
+}
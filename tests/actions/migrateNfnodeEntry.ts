@@ -0,0 +1,19 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function migrateNfnodeEntry(
+  program: Program<RewardSystem>,
+  payerKeypair: Keypair,
+  nftMint: PublicKey
+) {
+  await program.methods
+    .migrateNfnodeEntry()
+    .accounts({
+      payer: payerKeypair.publicKey,
+      nftMintAddress: nftMint,
+    })
+    .signers([payerKeypair])
+    .rpc({ commitment: "confirmed" });
+}
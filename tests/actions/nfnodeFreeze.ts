@@ -0,0 +1,51 @@
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function freezeNfnode(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  nftMint: PublicKey,
+  reason: number
+) {
+  await program.methods
+    .freezeNfnode(reason)
+    .accounts({
+      user: adminKeypair.publicKey,
+      nftMintAddress: nftMint,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function unfreezeNfnode(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  nftMint: PublicKey
+) {
+  await program.methods
+    .unfreezeNfnode()
+    .accounts({
+      user: adminKeypair.publicKey,
+      nftMintAddress: nftMint,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function appealNfnodeFreeze(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  nftMint: PublicKey,
+  userNftTokenAccount: PublicKey | null = null
+) {
+  await program.methods
+    .appealNfnodeFreeze()
+    .accounts({
+      user: userKeypair.publicKey,
+      nftMintAddress: nftMint,
+      userNftTokenAccount,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -19,5 +19,5 @@ export async function pauseProgram(
   const programState = await program.account.adminAccount.fetch(
     adminAccountPDA
   );
-  expect(programState.paused).to.be.true;
+  expect(programState.pauseFlags).to.equal(0x1f);
 }
\ No newline at end of file
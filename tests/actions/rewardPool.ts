@@ -0,0 +1,82 @@
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getRecipientBlacklistEntryPDA } from "../utils";
+
+export async function initializeRewardPool(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  mint: PublicKey,
+  protocolFeeBps: number,
+  burnBps: number,
+  treasuryTokenAccount: PublicKey | null = null
+) {
+  await program.methods
+    .initializeRewardPool(protocolFeeBps, burnBps)
+    .accounts({
+      user: adminKeypair.publicKey,
+      tokenMint: mint,
+      treasuryTokenAccount,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function setRewardPoolFees(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  mint: PublicKey,
+  protocolFeeBps: number,
+  burnBps: number,
+  treasuryTokenAccount: PublicKey | null = null
+) {
+  await program.methods
+    .setRewardPoolFees(protocolFeeBps, burnBps)
+    .accounts({
+      user: adminKeypair.publicKey,
+      tokenMint: mint,
+      treasuryTokenAccount,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function setReserveRatio(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  reserveRatioBps: number
+) {
+  await program.methods
+    .setReserveRatio(reserveRatioBps)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function claimOwed(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  mint: PublicKey,
+  nftMint: PublicKey,
+  role: { owner: {} } | { host: {} } | { manufacturer: {} } = { owner: {} },
+  recipientTokenAccount: PublicKey | null = null,
+  depositStorageAccount: PublicKey | null = null,
+  treasuryTokenAccount: PublicKey | null = null
+) {
+  await program.methods
+    .claimOwed(role)
+    .accounts({
+      user: userKeypair.publicKey,
+      nftMintAddress: nftMint,
+      tokenMint: mint,
+      treasuryTokenAccount,
+      stakeVaultAccount: null,
+      recipientTokenAccount,
+      recipientBlacklistEntry: await getRecipientBlacklistEntryPDA(program, recipientTokenAccount),
+      depositStorageAccount,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -0,0 +1,57 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function createRewardStream(
+  program: Program<RewardSystem>,
+  treasuryKeypair: Keypair,
+  mint: PublicKey,
+  amount: anchor.BN,
+  ratePerSecond: anchor.BN
+) {
+  await program.methods
+    .createRewardStream(amount, ratePerSecond)
+    .accounts({
+      treasury: treasuryKeypair.publicKey,
+      tokenMint: mint,
+    })
+    .signers([treasuryKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function drip(
+  program: Program<RewardSystem>,
+  crankerKeypair: Keypair,
+  treasury: PublicKey,
+  mint: PublicKey
+) {
+  const [rewardStreamPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("reward_stream"), treasury.toBuffer()],
+    program.programId
+  );
+  await program.methods
+    .drip()
+    .accounts({
+      cranker: crankerKeypair.publicKey,
+      rewardStream: rewardStreamPDA,
+      tokenMint: mint,
+    })
+    .signers([crankerKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function closeRewardStream(
+  program: Program<RewardSystem>,
+  treasuryKeypair: Keypair,
+  mint: PublicKey
+) {
+  await program.methods
+    .closeRewardStream()
+    .accounts({
+      treasury: treasuryKeypair.publicKey,
+      tokenMint: mint,
+    })
+    .signers([treasuryKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -0,0 +1,20 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair } from "@solana/web3.js";
+
+export async function setClaimPeriod(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  mode: { calendarAligned: {} } | { rolling: {} },
+  periodSeconds: anchor.BN,
+  offsetSeconds: anchor.BN = new anchor.BN(0)
+) {
+  await program.methods
+    .setClaimPeriod(mode, periodSeconds, offsetSeconds)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
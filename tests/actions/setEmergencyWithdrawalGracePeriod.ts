@@ -0,0 +1,18 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair } from "@solana/web3.js";
+
+export async function setEmergencyWithdrawalGracePeriod(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  gracePeriod: anchor.BN
+) {
+  await program.methods
+    .setEmergencyWithdrawalGracePeriod(gracePeriod)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
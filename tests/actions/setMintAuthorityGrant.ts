@@ -0,0 +1,21 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function setMintAuthorityGrant(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  mintAuthority: PublicKey,
+  registrationOpensAt: anchor.BN,
+  registrationClosesAt: anchor.BN,
+  allowedNfnodeTypes: any[]
+) {
+  await program.methods
+    .setMintAuthorityGrant(mintAuthority, registrationOpensAt, registrationClosesAt, allowedNfnodeTypes)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -0,0 +1,19 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function setNfnodeCollection(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  collectionMint: PublicKey | null
+) {
+  await program.methods
+    .setNfnodeCollection()
+    .accounts({
+      user: adminKeypair.publicKey,
+      collectionMint,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -0,0 +1,27 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair } from "@solana/web3.js";
+
+// AdminAccount.pause_flags bits
+export const PAUSE_CLAIMS = 1 << 0;
+export const PAUSE_DEPOSITS = 1 << 1;
+export const PAUSE_WITHDRAWALS = 1 << 2;
+export const PAUSE_REGISTRATION = 1 << 3;
+export const PAUSE_FUNDING = 1 << 4;
+
+export async function setPauseFlags(
+  program: Program<RewardSystem>,
+  adminKeypair: Keypair,
+  pauseFlags: number,
+  pauseReason: number = 0,
+  pauseExpiresAt: anchor.BN = new anchor.BN(0)
+) {
+  await program.methods
+    .setPauseFlags(pauseFlags, pauseReason, pauseExpiresAt)
+    .accounts({
+      user: adminKeypair.publicKey,
+    })
+    .signers([adminKeypair])
+    .rpc({ commitment: "confirmed" });
+}
@@ -0,0 +1,25 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+export async function setPayoutDestination(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  nftMint: PublicKey,
+  role: { owner: {} } | { host: {} } | { manufacturer: {} },
+  destination: PublicKey,
+  userNFTTokenAccount: PublicKey | null = null
+) {
+  await program.methods
+    .setPayoutDestination(role, destination)
+    .accounts({
+      user: userKeypair.publicKey,
+      nftMintAddress: nftMint,
+      userNftTokenAccount: userNFTTokenAccount,
+      tokenProgram2022: TOKEN_2022_PROGRAM_ID,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
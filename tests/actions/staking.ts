@@ -0,0 +1,91 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function setStakeCommission(
+  program: Program<RewardSystem>,
+  ownerKeypair: Keypair,
+  nftMint: PublicKey,
+  userNFTTokenAccount: PublicKey,
+  commissionBps: number,
+  stakeCap: anchor.BN
+) {
+  await program.methods
+    .setStakeCommission(commissionBps, stakeCap)
+    .accounts({
+      user: ownerKeypair.publicKey,
+      nftMintAddress: nftMint,
+      userNftTokenAccount: userNFTTokenAccount,
+    })
+    .signers([ownerKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function stake(
+  program: Program<RewardSystem>,
+  delegatorKeypair: Keypair,
+  mint: PublicKey,
+  nftMint: PublicKey,
+  amount: anchor.BN
+) {
+  await program.methods
+    .stake(amount)
+    .accounts({
+      delegator: delegatorKeypair.publicKey,
+      nftMintAddress: nftMint,
+      tokenMint: mint,
+    })
+    .signers([delegatorKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function unstake(
+  program: Program<RewardSystem>,
+  delegatorKeypair: Keypair,
+  nftMint: PublicKey,
+  amount: anchor.BN
+) {
+  await program.methods
+    .unstake(amount)
+    .accounts({
+      delegator: delegatorKeypair.publicKey,
+      nftMintAddress: nftMint,
+    })
+    .signers([delegatorKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function withdrawUnstaked(
+  program: Program<RewardSystem>,
+  delegatorKeypair: Keypair,
+  mint: PublicKey,
+  nftMint: PublicKey
+) {
+  await program.methods
+    .withdrawUnstaked()
+    .accounts({
+      delegator: delegatorKeypair.publicKey,
+      nftMintAddress: nftMint,
+      tokenMint: mint,
+    })
+    .signers([delegatorKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function claimStakeRewards(
+  program: Program<RewardSystem>,
+  delegatorKeypair: Keypair,
+  mint: PublicKey,
+  nftMint: PublicKey
+) {
+  await program.methods
+    .claimStakeRewards()
+    .accounts({
+      delegator: delegatorKeypair.publicKey,
+      nftMintAddress: nftMint,
+      tokenMint: mint,
+    })
+    .signers([delegatorKeypair])
+    .rpc({ commitment: "confirmed" });
+}
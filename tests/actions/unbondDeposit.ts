@@ -0,0 +1,56 @@
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+export async function requestWithdrawal(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  nftMint: PublicKey,
+  userNFTTokenAccount: PublicKey | null // null for a depositor who sold the nft
+) {
+  await program.methods
+    .requestWithdrawal()
+    .accounts({
+      user: userKeypair.publicKey,
+      nftMintAddress: nftMint,
+      userNftTokenAccount: userNFTTokenAccount,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function cancelWithdrawal(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  nftMint: PublicKey,
+  userNFTTokenAccount: PublicKey | null // null for a depositor who sold the nft
+) {
+  await program.methods
+    .cancelWithdrawal()
+    .accounts({
+      user: userKeypair.publicKey,
+      nftMintAddress: nftMint,
+      userNftTokenAccount: userNFTTokenAccount,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
+
+export async function completeWithdrawal(
+  program: Program<RewardSystem>,
+  userKeypair: Keypair,
+  mint: PublicKey,
+  nftMint: PublicKey,
+  userNFTTokenAccount: PublicKey | null // null for a depositor who sold the nft
+) {
+  await program.methods
+    .completeWithdrawal()
+    .accounts({
+      user: userKeypair.publicKey,
+      tokenMint: mint,
+      nftMintAddress: nftMint,
+      userNftTokenAccount: userNFTTokenAccount,
+    })
+    .signers([userKeypair])
+    .rpc({ commitment: "confirmed" });
+}
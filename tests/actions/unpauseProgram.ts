@@ -20,5 +20,5 @@ export async function unpauseProgram(
     adminAccountPDA,
     "finalized"
   );
-  expect(programState.paused).to.be.false;
+  expect(programState.pauseFlags).to.equal(0);
 }
\ No newline at end of file
@@ -3,6 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { RewardSystem } from "../../target/types/reward_system";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { getMintAuthorityEntryPDA } from "../utils";
 import { expect } from "chai";
 
 export async function updateNfnode(
@@ -21,6 +22,7 @@ export async function updateNfnode(
       user: userKeypair.publicKey,
       host: user2Keypair.publicKey,
       nftMintAddress: nftMint,
+      mintAuthorityEntry: await getMintAuthorityEntryPDA(program, nftMint),
       userNftTokenAccount: userNFTTokenAccount,
       tokenProgram2022: TOKEN_2022_PROGRAM_ID,
     })
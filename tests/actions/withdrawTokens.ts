@@ -3,20 +3,23 @@ import { Program } from "@coral-xyz/anchor";
 import { RewardSystem } from "../../target/types/reward_system";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { getMintAuthorityEntryPDA } from "../utils";
 
 export async function withdrawTokens(
     program: Program<RewardSystem>,
     userKeypair: Keypair,
     mint: PublicKey,
     nftMint: PublicKey,
-    userNFTTokenAccount: PublicKey
+    userNFTTokenAccount: PublicKey | null, // null for a depositor who sold the nft
+    amount: anchor.BN = new anchor.BN(5000000000)
 ) {
     await program.methods
-        .withdrawTokens()
+        .withdrawTokens(amount)
         .accounts({
             user: userKeypair.publicKey,
             tokenMint: mint,
             nftMintAddress: nftMint,
+            mintAuthorityEntry: await getMintAuthorityEntryPDA(program, nftMint),
             tokenProgram2022: TOKEN_2022_PROGRAM_ID,
             userNftTokenAccount: userNFTTokenAccount
         })
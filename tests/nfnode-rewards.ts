@@ -4,9 +4,21 @@ import { RewardSystem } from "../target/types/reward_system";
 import { PublicKey, Keypair, Connection } from "@solana/web3.js";
 import * as dotenv from "dotenv";
 import { expect } from "chai";
+import {
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
 
 import {
   setupTests,
+  getH3RegionAddress,
+  createCollectionMint,
+  createRevokedCollectionNft,
+  airdropSolIfNeeded,
+  CORE_ASSET,
+  CORE_ASSET_OWNER,
+  CORE_COLLECTION,
 } from "./utils";
 import {
   initializeSystem,
@@ -22,7 +34,53 @@ import {
   depositTokens,
   withdrawTokens,
   addMintAuthority,
-  removeMintAuthority
+  removeMintAuthority,
+  initializeHost,
+  hostClaimAll,
+  setPayoutDestination,
+  crankClaimRewards,
+  setAutoCompound,
+  migrateNfnodeEntry,
+  migrateHost,
+  setMintAuthorityGrant,
+  setNfnodeCollection,
+  addIdentitySource,
+  removeIdentitySource,
+  bindDevice,
+  rebindDevice,
+  submitHeartbeat,
+  assertLocation,
+  setH3RegionMultiplier,
+  setClaimPeriod,
+  setEmergencyWithdrawalGracePeriod,
+  setPauseFlags,
+  PAUSE_DEPOSITS,
+  PAUSE_FUNDING,
+  freezeNfnode,
+  unfreezeNfnode,
+  appealNfnodeFreeze,
+  blacklistWallet,
+  removeBlacklistedWallet,
+  appealBlacklist,
+  initializeRewardPool,
+  setRewardPoolFees,
+  createRewardStream,
+  drip,
+  closeRewardStream,
+  setReserveRatio,
+  claimOwed,
+  setStakeCommission,
+  stake,
+  unstake,
+  withdrawUnstaked,
+  requestWithdrawal,
+  completeWithdrawal,
+  setDepositCarry,
+  getIdentitySourcePDA,
+  assetSetStakeCommission,
+  initializeAssetNfnode,
+  assetSetDepositCarry,
+  assetCompleteWithdrawal,
 } from "./actions";
 
 describe("nfnode-rewards", async () => {
@@ -47,7 +105,8 @@ describe("nfnode-rewards", async () => {
     user2TokenAccount: PublicKey,
     tokenStoragePDA: PublicKey,
     adminAccountPDA: PublicKey,
-    nfnodeEntryPDA: PublicKey;
+    nfnodeEntryPDA: PublicKey,
+    mintAuthorityToRemove: PublicKey;
 
   before(async () => {
     const setupResult = await setupTests(provider, program);
@@ -88,28 +147,96 @@ describe("nfnode-rewards", async () => {
   it("Accept admin request", async () => {
     await acceptAdmin(program, adminKeypair, adminAccountPDA);
   });
+  it("Initialize reward pool", async () => {
+    await initializeRewardPool(program, adminKeypair, mint, 0, 0);
+    const [rewardPoolPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_pool")],
+      program.programId
+    );
+    const rewardPoolState = await program.account.rewardPool.fetch(rewardPoolPDA);
+    expect(rewardPoolState.totalFunded.toNumber()).to.equal(0);
+    expect(rewardPoolState.protocolFeeBps).to.equal(0);
+  });
   it("Add mint authority to be removed", async () => {
-    const newMintAuthority = Keypair.generate().publicKey;
-    await addMintAuthority(program, adminKeypair, newMintAuthority, adminAccountPDA);
-    const adminAccountState = await program.account.adminAccount.fetch(adminAccountPDA);
-    const mintAuthorities = adminAccountState.mintAuthorities.map((mintAuthority) => mintAuthority.toBase58());
-    expect(mintAuthorities).to.include(newMintAuthority.toBase58());
+    mintAuthorityToRemove = Keypair.generate().publicKey;
+    await addMintAuthority(program, adminKeypair, mintAuthorityToRemove);
+    const [mintAuthorityEntryPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), mintAuthorityToRemove.toBuffer()],
+      program.programId
+    );
+    const mintAuthorityEntryState = await program.account.mintAuthorityEntry.fetch(mintAuthorityEntryPDA);
+    expect(mintAuthorityEntryState.authority.toBase58()).to.equal(mintAuthorityToRemove.toBase58());
   });
   
   it("Remove mint authority", async () => {
-    const adminAccountStatePrev = await program.account.adminAccount.fetch(adminAccountPDA);
-    const mintAuthorityToRemove = adminAccountStatePrev.mintAuthorities[1];
     await removeMintAuthority(program, adminKeypair, mintAuthorityToRemove);
-    const adminAccountState = await program.account.adminAccount.fetch(adminAccountPDA);
-    const mintAuthorities = adminAccountState.mintAuthorities.map((mintAuthority) => mintAuthority.toBase58());
-    expect(mintAuthorities).to.not.include(mintAuthorityToRemove.toBase58());
+    const [mintAuthorityEntryPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), mintAuthorityToRemove.toBuffer()],
+      program.programId
+    );
+    const mintAuthorityEntryState = await program.account.mintAuthorityEntry.fetchNullable(mintAuthorityEntryPDA);
+    expect(mintAuthorityEntryState).to.be.null;
   });
   it("Add final mint authority", async () => {
     const newMintAuthority = adminKeypair.publicKey;
-    await addMintAuthority(program, adminKeypair, newMintAuthority, adminAccountPDA);
+    await addMintAuthority(program, adminKeypair, newMintAuthority);
+    const [mintAuthorityEntryPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), newMintAuthority.toBuffer()],
+      program.programId
+    );
+    const mintAuthorityEntryState = await program.account.mintAuthorityEntry.fetch(mintAuthorityEntryPDA);
+    expect(mintAuthorityEntryState.authority.toBase58()).to.equal(newMintAuthority.toBase58());
+  });
+  it("Attempt to set a mint authority grant ending before it starts (should fail)", async () => {
+    let grantError = null;
+    try {
+      await setMintAuthorityGrant(
+        program,
+        adminKeypair,
+        adminKeypair.publicKey,
+        new anchor.BN(2000000000),
+        new anchor.BN(1000000000),
+        [{ byod: {} }]
+      );
+    } catch (error) {
+      grantError = error;
+    }
+
+    expect(grantError).to.not.be.null;
+    expect(grantError.message).to.include("Invalid mint authority grant.");
+  });
+  it("Set mint authority grant", async () => {
+    await setMintAuthorityGrant(
+      program,
+      adminKeypair,
+      adminKeypair.publicKey,
+      new anchor.BN(0),
+      new anchor.BN(0),
+      [{ don: {} }, { byod: {} }, { wayruHotspot: {} }]
+    );
+    const [mintAuthorityEntryPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), adminKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    const mintAuthorityEntryState = await program.account.mintAuthorityEntry.fetch(mintAuthorityEntryPDA);
+    expect(mintAuthorityEntryState.allowedNfnodeTypes).to.equal(0b111);
+    expect(mintAuthorityEntryState.registrationClosesAt.toNumber()).to.equal(0);
+  });
+  it("Attempt to set a collection mint without the token group extension (should fail)", async () => {
+    let collectionError = null;
+    try {
+      await setNfnodeCollection(program, adminKeypair, mint);
+    } catch (error) {
+      collectionError = error;
+    }
+
+    expect(collectionError).to.not.be.null;
+    expect(collectionError.message).to.include("Invalid collection.");
+  });
+  it("Clear nfnode collection", async () => {
+    await setNfnodeCollection(program, adminKeypair, null);
     const adminAccountState = await program.account.adminAccount.fetch(adminAccountPDA);
-    const mintAuthorities = adminAccountState.mintAuthorities.map((mintAuthority) => mintAuthority.toBase58());
-    expect(mintAuthorities).to.include(newMintAuthority.toBase58());
+    expect(adminAccountState.collection.toBase58()).to.equal(PublicKey.default.toBase58());
   });
   it("Initialize Nfnode byod", async () => {
     let error = null;
@@ -133,17 +260,21 @@ describe("nfnode-rewards", async () => {
     // expect(claimError.message).to.include("Deposit already made.");
 
   });
-  it("Attempt to Deposit twice (should fail)", async () => {
+  it("Attempt to Deposit zero tokens (should fail)", async () => {
     let claimError = null;
     try {
-      await depositTokens(program, userKeypair, mint, nftMint, userNFTTokenAccount);
+      await depositTokens(program, userKeypair, mint, nftMint, userNFTTokenAccount, new anchor.BN(0));
     } catch (error) {
       claimError = error;
     }
     expect(claimError).to.not.be.null;
-    expect(claimError.message).to.include("Deposit already made.");
+    expect(claimError.message).to.include("Deposit amount must be greater than zero.");
 
   });
+  it("Deposit made without a lockup choice is on the 30 day tier", async () => {
+    const nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntryPDA);
+    expect(nfnodeEntryState.lockupTier).to.deep.equal({ days30: {} });
+  });
 
   it("Should update nfnode entry", async () => {
     await updateNfnode(
@@ -173,6 +304,27 @@ describe("nfnode-rewards", async () => {
   it("Fund Token Storage", async () => {
     await fundTokenStorage(program, adminKeypair, mint, new anchor.BN(500000000));
   });
+  it("Drip a treasury stream into the reward pool", async () => {
+    const [rewardStreamPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_stream"), adminKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    await createRewardStream(program, adminKeypair, mint, new anchor.BN(100000000), new anchor.BN(1000000));
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    await drip(program, userKeypair, adminKeypair.publicKey, mint);
+    const rewardStreamState = await program.account.rewardStream.fetch(rewardStreamPDA);
+    expect(rewardStreamState.releasedAmount.toNumber()).to.be.greaterThan(0);
+    expect(rewardStreamState.releasedAmount.toNumber()).to.be.at.most(100000000);
+  });
+  it("Close the treasury stream and return what is left", async () => {
+    const [rewardStreamPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_stream"), adminKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    await closeRewardStream(program, adminKeypair, mint);
+    const rewardStreamState = await program.account.rewardStream.fetchNullable(rewardStreamPDA);
+    expect(rewardStreamState).to.be.null;
+  });
 
   it("Claim Rewards without admin signature must fail", async () => {
     let errorOccurred = false;
@@ -259,7 +411,7 @@ describe("nfnode-rewards", async () => {
     expect(claimError).to.not.be.null;
 
   });
-  it("Attempt Withdraw  after unpaused and before 30 days (should fail)", async () => {
+  it("Attempt to withdraw the required deposit without unbonding (should fail)", async () => {
     let claimError = null;
     try {
       await withdrawTokens(program, userKeypair, mint, nftMint, userNFTTokenAccount);
@@ -267,8 +419,30 @@ describe("nfnode-rewards", async () => {
       claimError = error;
     }
     expect(claimError).to.not.be.null;
+    expect(claimError.message).to.include("The required deposit can only be withdrawn by unbonding.");
+
+  });
+  it("Attempt to request a withdrawal before 30 days (should fail)", async () => {
+    let claimError = null;
+    try {
+      await requestWithdrawal(program, userKeypair, nftMint, userNFTTokenAccount);
+    } catch (error) {
+      claimError = error;
+    }
+    expect(claimError).to.not.be.null;
     expect(claimError.message).to.include("Withdraw too early.");
 
+  });
+  it("Attempt to complete a withdrawal that was never requested (should fail)", async () => {
+    let claimError = null;
+    try {
+      await completeWithdrawal(program, userKeypair, mint, nftMint, userNFTTokenAccount);
+    } catch (error) {
+      claimError = error;
+    }
+    expect(claimError).to.not.be.null;
+    expect(claimError.message).to.include("NfNode deposit is not unbonding.");
+
   });
 
   it("Attempt to Claim Rewards With no nft (should fail)", async () => {
@@ -363,4 +537,755 @@ describe("nfnode-rewards", async () => {
     expect(claimError).to.not.be.null;
     expect(claimError.message).to.include("Claim already made today.");
   });
+  it("Initialize host profile", async () => {
+    await initializeHost(program, user2Keypair);
+    const [hostPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("host"), user2Keypair.publicKey.toBuffer()],
+      program.programId
+    );
+    const hostState = await program.account.host.fetch(hostPDA);
+    expect(hostState.host.toBase58()).to.equal(user2Keypair.publicKey.toBase58());
+  });
+
+  it("Attempt host claim all with missing nfnode accounts (should fail)", async () => {
+    let claimError = null;
+    try {
+      await hostClaimAll(
+        program,
+        provider,
+        adminKeypair,
+        user2Keypair,
+        mint,
+        [nftMint],
+        [new anchor.BN(100000000), new anchor.BN(100000000)],
+        new anchor.BN(1)
+      );
+    } catch (error) {
+      claimError = error;
+    }
+
+    expect(claimError).to.not.be.null;
+    expect(claimError.message).to.include("Invalid remaining accounts.");
+  });
+
+  it("Attempt host claim all after host already claimed today (should fail)", async () => {
+    let claimError = null;
+    try {
+      await hostClaimAll(
+        program,
+        provider,
+        adminKeypair,
+        user2Keypair,
+        mint,
+        [nftMint],
+        [new anchor.BN(100000000)],
+        new anchor.BN(1)
+      );
+    } catch (error) {
+      claimError = error;
+    }
+
+    expect(claimError).to.not.be.null;
+    expect(claimError.message).to.include("Claim already made today.");
+  });
+  it("Attempt to set host payout destination as non host (should fail)", async () => {
+    let payoutError = null;
+    try {
+      await setPayoutDestination(program, userKeypair, nftMint, { host: {} }, adminTokenAccount);
+    } catch (error) {
+      payoutError = error;
+    }
+
+    expect(payoutError).to.not.be.null;
+    expect(payoutError.message).to.include("Unauthorized access user.");
+  });
+
+  it("Set host payout destination", async () => {
+    await setPayoutDestination(program, user2Keypair, nftMint, { host: {} }, user2TokenAccount);
+    const [payoutConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("payout_config"), nftMint.toBuffer()],
+      program.programId
+    );
+    const payoutConfigState = await program.account.payoutConfig.fetch(payoutConfigPDA);
+    expect(payoutConfigState.host.beneficiary.toBase58()).to.equal(user2Keypair.publicKey.toBase58());
+    expect(payoutConfigState.host.destination.toBase58()).to.equal(user2TokenAccount.toBase58());
+  });
+  it("Attempt crank claim with crank fee above the reward (should fail)", async () => {
+    let claimError = null;
+    try {
+      await crankClaimRewards(
+        program,
+        provider,
+        adminKeypair,
+        userKeypair,
+        user2Keypair.publicKey,
+        { manufacturer: {} },
+        mint,
+        nftMint,
+        null,
+        new anchor.BN(100000000),
+        new anchor.BN(32360),
+        new anchor.BN(100000000),
+        userTokenAccount
+      );
+    } catch (error) {
+      claimError = error;
+    }
+
+    expect(claimError).to.not.be.null;
+    expect(claimError.message).to.include("Crank fee must be lower than the reward amount.");
+  });
+
+  it("Attempt crank claim for a beneficiary that already claimed today (should fail)", async () => {
+    let claimError = null;
+    try {
+      await crankClaimRewards(
+        program,
+        provider,
+        adminKeypair,
+        userKeypair,
+        user2Keypair.publicKey,
+        { manufacturer: {} },
+        mint,
+        nftMint,
+        null,
+        new anchor.BN(100000000),
+        new anchor.BN(32361),
+        new anchor.BN(1000000),
+        userTokenAccount,
+        user2TokenAccount
+      );
+    } catch (error) {
+      claimError = error;
+    }
+
+    expect(claimError).to.not.be.null;
+    expect(claimError.message).to.include("Claim already made today.");
+  });
+  it("Attempt to set auto compound above 100% (should fail)", async () => {
+    let compoundError = null;
+    try {
+      await setAutoCompound(program, userKeypair, nft2Mint, userNFT2TokenAccount, 10001, false);
+    } catch (error) {
+      compoundError = error;
+    }
+
+    expect(compoundError).to.not.be.null;
+    expect(compoundError.message).to.include("Compound share must be at most 10000 basis points.");
+  });
+
+  it("Set auto compound on nfnode", async () => {
+    await setAutoCompound(program, userKeypair, nft2Mint, userNFT2TokenAccount, 2000, false);
+    const [autoCompoundPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("auto_compound"), nft2Mint.toBuffer()],
+      program.programId
+    );
+    const autoCompoundState = await program.account.autoCompound.fetch(autoCompoundPDA);
+    expect(autoCompoundState.owner.toBase58()).to.equal(userKeypair.publicKey.toBase58());
+    expect(autoCompoundState.compoundBps).to.equal(2000);
+    expect(autoCompoundState.stakeBeyondRequirement).to.be.false;
+    expect(autoCompoundState.enabledAt.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("Attempt to migrate an nfnode entry already on the current layout (should fail)", async () => {
+    let migrateError = null;
+    try {
+      await migrateNfnodeEntry(program, userKeypair, nft2Mint);
+    } catch (error) {
+      migrateError = error;
+    }
+
+    expect(migrateError).to.not.be.null;
+    expect(migrateError.message).to.include("Account already migrated.");
+  });
+
+  it("Attempt to migrate a host account already on the current layout (should fail)", async () => {
+    let migrateError = null;
+    try {
+      await migrateHost(program, userKeypair, user2Keypair.publicKey);
+    } catch (error) {
+      migrateError = error;
+    }
+
+    expect(migrateError).to.not.be.null;
+    expect(migrateError.message).to.include("Account already migrated.");
+  });
+
+  it("Attempt to add a token-2022 identity source (should fail)", async () => {
+    let sourceError = null;
+    try {
+      await addIdentitySource(program, adminKeypair, Keypair.generate().publicKey, { token2022: {} });
+    } catch (error) {
+      sourceError = error;
+    }
+
+    expect(sourceError).to.not.be.null;
+    expect(sourceError.message).to.include("Invalid identity source.");
+  });
+  it("Add and remove a Metaplex Core identity source", async () => {
+    const coreCollection = Keypair.generate().publicKey;
+    const [identitySourcePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("identity_source"), coreCollection.toBuffer()],
+      program.programId
+    );
+    await addIdentitySource(program, adminKeypair, coreCollection, { coreAsset: {} });
+    const identitySourceState = await program.account.identitySource.fetch(identitySourcePDA);
+    expect(identitySourceState.source.toBase58()).to.equal(coreCollection.toBase58());
+    expect(identitySourceState.identity).to.deep.equal({ coreAsset: {} });
+
+    await removeIdentitySource(program, adminKeypair, coreCollection);
+    const removedState = await program.account.identitySource.fetchNullable(identitySourcePDA);
+    expect(removedState).to.be.null;
+  });
+  it("Attempt an asset owner instruction on a Token-2022 NfNode (should fail)", async () => {
+    const coreCollection = Keypair.generate().publicKey;
+    await addIdentitySource(program, adminKeypair, coreCollection, { coreAsset: {} });
+    let commissionError = null;
+    try {
+      await assetSetStakeCommission(
+        program,
+        userKeypair,
+        nft2Mint,
+        getIdentitySourcePDA(program, coreCollection),
+        1000,
+        new anchor.BN(0)
+      );
+    } catch (error) {
+      commissionError = error;
+    }
+    await removeIdentitySource(program, adminKeypair, coreCollection);
+
+    expect(commissionError).to.not.be.null;
+    expect(commissionError.message).to.include("Invalid identity source.");
+  });
+
+  it("Attempt to bind a device as a non manufacturer (should fail)", async () => {
+    let bindError = null;
+    try {
+      await bindDevice(program, userKeypair, nft2Mint, Keypair.generate().publicKey);
+    } catch (error) {
+      bindError = error;
+    }
+
+    expect(bindError).to.not.be.null;
+    expect(bindError.message).to.include("Unauthorized access user.");
+  });
+  it("Bind device and submit a device signed heartbeat", async () => {
+    const deviceKeypair = Keypair.generate();
+    await bindDevice(program, user2Keypair, nft2Mint, deviceKeypair.publicKey);
+    const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+    await submitHeartbeat(program, userKeypair, deviceKeypair, nft2Mint, timestamp);
+    const [nfnodeEntry2PDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nfnode_entry"), nft2Mint.toBuffer()],
+      program.programId
+    );
+    const nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntry2PDA);
+    expect(nfnodeEntryState.devicePubkey.toBase58()).to.equal(deviceKeypair.publicKey.toBase58());
+    expect(nfnodeEntryState.lastHeartbeatTimestamp.toNumber()).to.equal(timestamp.toNumber());
+  });
+  it("Rebind a replacement device as the admin", async () => {
+    const [nfnodeEntry2PDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nfnode_entry"), nft2Mint.toBuffer()],
+      program.programId
+    );
+    const replacementKeypair = Keypair.generate();
+    await rebindDevice(program, adminKeypair, nft2Mint, replacementKeypair.publicKey);
+    let nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntry2PDA);
+    expect(nfnodeEntryState.devicePubkey.toBase58()).to.equal(replacementKeypair.publicKey.toBase58());
+    expect(nfnodeEntryState.lastHeartbeatTimestamp.toNumber()).to.equal(0);
+
+    // the replacement proves it is online before the next claim
+    const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+    await submitHeartbeat(program, userKeypair, replacementKeypair, nft2Mint, timestamp);
+    nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntry2PDA);
+    expect(nfnodeEntryState.lastHeartbeatTimestamp.toNumber()).to.equal(timestamp.toNumber());
+  });
+
+  it("Attempt to set a region multiplier above the maximum (should fail)", async () => {
+    let multiplierError = null;
+    try {
+      await setH3RegionMultiplier(program, adminKeypair, new anchor.BN("85283473fffffff", 16), 30001);
+    } catch (error) {
+      multiplierError = error;
+    }
+
+    expect(multiplierError).to.not.be.null;
+    expect(multiplierError.message).to.include("Region multiplier must be at most 30000 basis points.");
+  });
+  it("Set a region multiplier", async () => {
+    const regionCell = new anchor.BN("85283473fffffff", 16);
+    await setH3RegionMultiplier(program, adminKeypair, regionCell, 15000);
+    const h3RegionState = await program.account.h3Region.fetch(
+      getH3RegionAddress(program, BigInt(regionCell.toString()))
+    );
+    expect(h3RegionState.multiplierBps).to.equal(15000);
+  });
+  it("Attempt to assert an invalid H3 cell (should fail)", async () => {
+    let locationError = null;
+    try {
+      await assertLocation(program, user2Keypair, mint, nft2Mint, new anchor.BN(0));
+    } catch (error) {
+      locationError = error;
+    }
+
+    expect(locationError).to.not.be.null;
+    expect(locationError.message).to.include("Invalid H3 cell.");
+  });
+
+  it("Attempt to set a rolling claim period with an offset (should fail)", async () => {
+    let periodError = null;
+    try {
+      await setClaimPeriod(program, adminKeypair, { rolling: {} }, new anchor.BN(86400), new anchor.BN(3600));
+    } catch (error) {
+      periodError = error;
+    }
+
+    expect(periodError).to.not.be.null;
+    expect(periodError.message).to.include("Invalid claim period.");
+  });
+  it("Set a calendar aligned claim period with an offset", async () => {
+    await setClaimPeriod(program, adminKeypair, { calendarAligned: {} }, new anchor.BN(86400), new anchor.BN(3600));
+    const adminAccountState = await program.account.adminAccount.fetch(adminAccountPDA);
+    expect(adminAccountState.claimPeriodMode).to.deep.equal({ calendarAligned: {} });
+    expect(adminAccountState.claimPeriodSeconds.toNumber()).to.equal(86400);
+    expect(adminAccountState.claimPeriodOffset.toNumber()).to.equal(3600);
+
+    await setClaimPeriod(program, adminKeypair, { calendarAligned: {} }, new anchor.BN(86400));
+  });
+
+  it("Attempt a host claim from a wallet that is not the host (should fail)", async () => {
+    let claimError = null;
+    try {
+      await othersClaimRewards(
+        program,
+        provider,
+        adminKeypair,
+        userKeypair,
+        mint,
+        nftMint,
+        new anchor.BN(100000000),
+        new anchor.BN(32360),
+        null,
+        "host"
+      );
+    } catch (error) {
+      claimError = error;
+    }
+
+    expect(claimError).to.not.be.null;
+    expect(claimError.message).to.include("Unauthorized access user.");
+  });
+
+  it("Attempt to set an emergency withdrawal grace period above 90 days (should fail)", async () => {
+    let graceError = null;
+    try {
+      await setEmergencyWithdrawalGracePeriod(program, adminKeypair, new anchor.BN(91 * 86400));
+    } catch (error) {
+      graceError = error;
+    }
+
+    expect(graceError).to.not.be.null;
+    expect(graceError.message).to.include("Invalid emergency withdrawal grace period.");
+  });
+  it("Pausing records the pause time", async () => {
+    await setEmergencyWithdrawalGracePeriod(program, adminKeypair, new anchor.BN(7 * 86400));
+    await pauseProgram(program, adminKeypair, adminAccountPDA);
+    const adminAccountState = await program.account.adminAccount.fetch(adminAccountPDA);
+    expect(adminAccountState.emergencyWithdrawalGracePeriod.toNumber()).to.equal(7 * 86400);
+    expect(adminAccountState.pausedAt.toNumber()).to.be.greaterThan(0);
+
+    await unpauseProgram(program, adminKeypair, adminAccountPDA);
+    const unpausedState = await program.account.adminAccount.fetch(adminAccountPDA);
+    expect(unpausedState.pausedAt.toNumber()).to.equal(0);
+  });
+
+  it("Attempt to set unknown pause flags (should fail)", async () => {
+    let pauseError = null;
+    try {
+      await setPauseFlags(program, adminKeypair, 1 << 5);
+    } catch (error) {
+      pauseError = error;
+    }
+
+    expect(pauseError).to.not.be.null;
+    expect(pauseError.message).to.include("Invalid pause flags or expiry.");
+  });
+  it("Pause funding and deposits with a reason and expiry", async () => {
+    const pauseExpiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    await setPauseFlags(program, adminKeypair, PAUSE_FUNDING | PAUSE_DEPOSITS, 7, pauseExpiresAt);
+    const adminAccountState = await program.account.adminAccount.fetch(adminAccountPDA);
+    expect(adminAccountState.pauseFlags).to.equal(PAUSE_FUNDING | PAUSE_DEPOSITS);
+    expect(adminAccountState.pauseReason).to.equal(7);
+    expect(adminAccountState.pauseExpiresAt.toNumber()).to.equal(pauseExpiresAt.toNumber());
+
+    let fundError = null;
+    try {
+      await fundTokenStorage(program, adminKeypair, mint, new anchor.BN(1000));
+    } catch (error) {
+      fundError = error;
+    }
+    expect(fundError).to.not.be.null;
+    expect(fundError.message).to.include("Program is paused.");
+
+    await setPauseFlags(program, adminKeypair, 0);
+  });
+
+  it("Freeze an NfNode, appeal as host and unfreeze", async () => {
+    const [nfnodeEntry2PDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nfnode_entry"), nft2Mint.toBuffer()],
+      program.programId
+    );
+    await freezeNfnode(program, adminKeypair, nft2Mint, 3);
+    let nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntry2PDA);
+    expect(nfnodeEntryState.frozen).to.be.true;
+    expect(nfnodeEntryState.freezeReason).to.equal(3);
+
+    await appealNfnodeFreeze(program, user2Keypair, nft2Mint);
+    nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntry2PDA);
+    expect(nfnodeEntryState.freezeAppealed).to.be.true;
+
+    let appealError = null;
+    try {
+      await appealNfnodeFreeze(program, user2Keypair, nft2Mint);
+    } catch (error) {
+      appealError = error;
+    }
+    expect(appealError).to.not.be.null;
+    expect(appealError.message).to.include("Appeal already filed.");
+
+    await unfreezeNfnode(program, adminKeypair, nft2Mint);
+    nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntry2PDA);
+    expect(nfnodeEntryState.frozen).to.be.false;
+    expect(nfnodeEntryState.freezeAppealed).to.be.false;
+  });
+  it("Blacklist a wallet, appeal and remove it", async () => {
+    const walletKeypair = Keypair.generate();
+    const [blacklistEntryPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), walletKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    await blacklistWallet(program, adminKeypair, walletKeypair.publicKey, 9);
+    await appealBlacklist(program, walletKeypair);
+    const blacklistEntryState = await program.account.blacklistEntry.fetch(blacklistEntryPDA);
+    expect(blacklistEntryState.reason).to.equal(9);
+    expect(blacklistEntryState.appealed).to.be.true;
+
+    await removeBlacklistedWallet(program, adminKeypair, walletKeypair.publicKey);
+    const removedState = await program.account.blacklistEntry.fetchNullable(blacklistEntryPDA);
+    expect(removedState).to.be.null;
+  });
+
+  it("Attempt to set a protocol fee and burn above 50% (should fail)", async () => {
+    try {
+      await setRewardPoolFees(program, adminKeypair, mint, 0, 5001);
+      expect.fail("Setting the protocol cut above the maximum should have failed");
+    } catch (error) {
+      expect(error.message).to.include("Protocol fee and burn must be at most 5000 basis points.");
+    }
+  });
+
+  it("Attempt to set a reserve ratio above 90% (should fail)", async () => {
+    try {
+      await setReserveRatio(program, adminKeypair, 9001);
+      expect.fail("Setting the reserve ratio above the maximum should have failed");
+    } catch (error) {
+      expect(error.message).to.include("Reserve ratio must be at most 9000 basis points.");
+    }
+  });
+
+  it("Attempt to claim owed rewards when nothing is owed (should fail)", async () => {
+    await setReserveRatio(program, adminKeypair, 1000);
+    const [rewardPoolPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_pool")],
+      program.programId
+    );
+    const rewardPoolState = await program.account.rewardPool.fetch(rewardPoolPDA);
+    expect(rewardPoolState.reserveRatioBps).to.equal(1000);
+    try {
+      await claimOwed(program, userKeypair, mint, nftMint);
+      expect.fail("Claiming owed rewards with nothing owed should have failed");
+    } catch (error) {
+      expect(error.message).to.include("Nothing is owed on this reward entry.");
+    }
+  });
+
+  it("Stake on an nfnode as a delegator and unstake", async () => {
+    const [nfnodeStakePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nfnode_stake"), nftMint.toBuffer()],
+      program.programId
+    );
+    const [stakePositionPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_position"), nftMint.toBuffer(), adminKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    await setStakeCommission(
+      program,
+      userKeypair,
+      nftMint,
+      userNFTTokenAccount,
+      1000,
+      new anchor.BN(1000000)
+    );
+    await stake(program, adminKeypair, mint, nftMint, new anchor.BN(1000000));
+    let nfnodeStakeState = await program.account.nfNodeStake.fetch(nfnodeStakePDA);
+    expect(nfnodeStakeState.commissionBps).to.equal(1000);
+    // new stake warms up before it shares in owner rewards
+    expect(nfnodeStakeState.totalStaked.toNumber()).to.equal(0);
+    expect(nfnodeStakeState.totalWarming.toNumber()).to.equal(1000000);
+
+    await unstake(program, adminKeypair, nftMint, new anchor.BN(1000000));
+    nfnodeStakeState = await program.account.nfNodeStake.fetch(nfnodeStakePDA);
+    expect(nfnodeStakeState.totalWarming.toNumber()).to.equal(0);
+    const stakePositionState = await program.account.stakePosition.fetch(stakePositionPDA);
+    expect(stakePositionState.warmingAmount.toNumber()).to.equal(0);
+    expect(stakePositionState.unstakingAmount.toNumber()).to.equal(1000000);
+  });
+
+  it("Attempt to stake above the owner's stake cap (should fail)", async () => {
+    try {
+      await stake(program, adminKeypair, mint, nftMint, new anchor.BN(1000001));
+      expect.fail("Staking above the stake cap should have failed");
+    } catch (error) {
+      expect(error.message).to.include("Stake would exceed the cap set by the nfnode owner.");
+    }
+  });
+
+  it("Delay a stake commission increase and apply a decrease right away", async () => {
+    const [nfnodeStakePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nfnode_stake"), nftMint.toBuffer()],
+      program.programId
+    );
+    const stakeCap = new anchor.BN(1000000);
+    await setStakeCommission(program, userKeypair, nftMint, userNFTTokenAccount, 2000, stakeCap);
+    let nfnodeStakeState = await program.account.nfNodeStake.fetch(nfnodeStakePDA);
+    expect(nfnodeStakeState.commissionBps).to.equal(1000);
+    expect(nfnodeStakeState.pendingCommissionBps).to.equal(2000);
+    expect(nfnodeStakeState.commissionEffectiveAt.toNumber()).to.be.greaterThan(0);
+
+    await setStakeCommission(program, userKeypair, nftMint, userNFTTokenAccount, 500, stakeCap);
+    nfnodeStakeState = await program.account.nfNodeStake.fetch(nfnodeStakePDA);
+    expect(nfnodeStakeState.commissionBps).to.equal(500);
+    expect(nfnodeStakeState.commissionEffectiveAt.toNumber()).to.equal(0);
+  });
+
+  it("Attempt to withdraw unstaked tokens during the cooldown (should fail)", async () => {
+    try {
+      await withdrawUnstaked(program, adminKeypair, mint, nftMint);
+      expect.fail("Withdrawing during the unstake cooldown should have failed");
+    } catch (error) {
+      expect(error.message).to.include("Unstaked tokens are still cooling down.");
+    }
+  });
+
+  it("Record the depositor of the nfnode deposit", async () => {
+    const nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntryPDA);
+    expect(nfnodeEntryState.depositor.toBase58()).to.equal(userKeypair.publicKey.toBase58());
+    expect(nfnodeEntryState.depositCarriesWithNft).to.be.false;
+  });
+
+  it("Attempt to set the deposit carry without holding the nft (should fail)", async () => {
+    try {
+      await setDepositCarry(program, user2Keypair, nftMint, null, true);
+      expect.fail("Setting the deposit carry without the nft should have failed");
+    } catch (error) {
+      expect(error.message).to.include("Insufficient NFT balance.");
+    }
+  });
+
+  it("Set the deposit to carry with the nft and back", async () => {
+    await setDepositCarry(program, userKeypair, nftMint, userNFTTokenAccount, true);
+    let nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntryPDA);
+    expect(nfnodeEntryState.depositCarriesWithNft).to.be.true;
+
+    await setDepositCarry(program, userKeypair, nftMint, userNFTTokenAccount, false);
+    nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntryPDA);
+    expect(nfnodeEntryState.depositCarriesWithNft).to.be.false;
+    expect(nfnodeEntryState.depositor.toBase58()).to.equal(userKeypair.publicKey.toBase58());
+  });
+
+  it("Register and deposit on an nfnode whose mint authority was revoked, through the collection", async () => {
+    const collectionMint = await createCollectionMint(provider.connection, adminKeypair);
+    await setNfnodeCollection(program, adminKeypair, collectionMint);
+    const { nftMint: revokedNftMint, nftTokenAccount: revokedNftTokenAccount } =
+      await createRevokedCollectionNft(
+        provider.connection,
+        adminKeypair,
+        userKeypair.publicKey,
+        collectionMint,
+        "don"
+      );
+    const [revokedNfnodeEntryPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nfnode_entry"), revokedNftMint.toBuffer()],
+      program.programId
+    );
+    await initializeNfnode(
+      program,
+      adminKeypair,
+      userKeypair,
+      user2Keypair,
+      revokedNftMint,
+      revokedNftTokenAccount,
+      revokedNfnodeEntryPDA,
+      mint,
+      { don: {} }
+    );
+    await depositTokens(program, userKeypair, mint, revokedNftMint, revokedNftTokenAccount, new anchor.BN(1000000));
+    const nfnodeEntryState = await program.account.nfNodeEntry.fetch(revokedNfnodeEntryPDA);
+    expect(nfnodeEntryState.depositAmount.toNumber()).to.equal(1000000);
+
+    await setNfnodeCollection(program, adminKeypair, null);
+  });
+
+  it("Release a frozen deposit once the emergency withdrawal grace period has passed", async () => {
+    await setEmergencyWithdrawalGracePeriod(program, adminKeypair, new anchor.BN(1));
+    await freezeNfnode(program, adminKeypair, nftMint, 1);
+    await pauseProgram(program, adminKeypair, adminAccountPDA);
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    // no request_withdrawal and no unbonding period, the lockup and the freeze do not hold the deposit
+    await completeWithdrawal(program, userKeypair, mint, nftMint, userNFTTokenAccount);
+    const nfnodeEntryState = await program.account.nfNodeEntry.fetch(nfnodeEntryPDA);
+    expect(nfnodeEntryState.depositAmount.toNumber()).to.equal(0);
+
+    await unpauseProgram(program, adminKeypair, adminAccountPDA);
+    await unfreezeNfnode(program, adminKeypair, nftMint);
+    await setEmergencyWithdrawalGracePeriod(program, adminKeypair, new anchor.BN(30 * 86400));
+  });
+
+  it("Tie an asset-node deposit to the asset, then release it to the proven asset holder", async () => {
+    const identitySource = getIdentitySourcePDA(program, CORE_COLLECTION);
+    const [assetEntryPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nfnode_entry"), CORE_ASSET.toBuffer()],
+      program.programId
+    );
+    await addIdentitySource(program, adminKeypair, CORE_COLLECTION, { coreAsset: {} });
+    await airdropSolIfNeeded(CORE_ASSET_OWNER, provider.connection);
+    const assetOwnerTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      CORE_ASSET_OWNER,
+      mint,
+      CORE_ASSET_OWNER.publicKey,
+      null,
+      null,
+      { commitment: "confirmed" }
+    ).then((account) => account.address);
+    await mintTo(
+      provider.connection,
+      adminKeypair,
+      mint,
+      assetOwnerTokenAccount,
+      adminKeypair,
+      5000000000,
+      [],
+      { commitment: "confirmed" }
+    );
+    await initializeAssetNfnode(
+      program,
+      adminKeypair,
+      CORE_ASSET_OWNER,
+      CORE_ASSET_OWNER.publicKey,
+      user2Keypair.publicKey,
+      mint,
+      CORE_ASSET,
+      identitySource,
+      { byod: {} }
+    );
+
+    // the token-2022 variant can not see who holds the asset, so it does not tie the deposit to it
+    let carryError = null;
+    try {
+      await setDepositCarry(program, CORE_ASSET_OWNER, CORE_ASSET, null, true);
+    } catch (error) {
+      carryError = error;
+    }
+    expect(carryError).to.not.be.null;
+    expect(carryError.message).to.include("Invalid identity source.");
+
+    await assetSetDepositCarry(program, CORE_ASSET_OWNER, CORE_ASSET, null, true);
+    let assetEntryState = await program.account.nfNodeEntry.fetch(assetEntryPDA);
+    expect(assetEntryState.depositCarriesWithNft).to.be.true;
+
+    // once tied to the asset the deposit only moves for a proven holder
+    await setEmergencyWithdrawalGracePeriod(program, adminKeypair, new anchor.BN(1));
+    await pauseProgram(program, adminKeypair, adminAccountPDA);
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    let releaseError = null;
+    try {
+      await assetCompleteWithdrawal(program, CORE_ASSET_OWNER, mint, CORE_ASSET, null);
+    } catch (error) {
+      releaseError = error;
+    }
+    expect(releaseError).to.not.be.null;
+    expect(releaseError.message).to.include("Invalid identity source.");
+
+    await assetCompleteWithdrawal(program, CORE_ASSET_OWNER, mint, CORE_ASSET, identitySource);
+    assetEntryState = await program.account.nfNodeEntry.fetch(assetEntryPDA);
+    expect(assetEntryState.depositAmount.toNumber()).to.equal(0);
+    expect(assetEntryState.depositCarriesWithNft).to.be.false;
+
+    await unpauseProgram(program, adminKeypair, adminAccountPDA);
+    await setEmergencyWithdrawalGracePeriod(program, adminKeypair, new anchor.BN(30 * 86400));
+  });
+
+  it("A one-unit deposit on the 365 day tier earns no lockup boost", async () => {
+    const collectionMint = await createCollectionMint(provider.connection, adminKeypair);
+    await setNfnodeCollection(program, adminKeypair, collectionMint);
+    const { nftMint: boostNftMint, nftTokenAccount: boostNftTokenAccount } =
+      await createRevokedCollectionNft(
+        provider.connection,
+        adminKeypair,
+        userKeypair.publicKey,
+        collectionMint,
+        "byod"
+      );
+    const [boostNfnodeEntryPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nfnode_entry"), boostNftMint.toBuffer()],
+      program.programId
+    );
+    const [boostDepositStorage] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_storage"), boostNftMint.toBuffer()],
+      program.programId
+    );
+    // auto-compounding waives the upfront deposit, so a single unit can be locked on the longest tier
+    await setAutoCompound(program, userKeypair, boostNftMint, boostNftTokenAccount, 10000, false);
+    await initializeNfnode(
+      program,
+      adminKeypair,
+      userKeypair,
+      user2Keypair,
+      boostNftMint,
+      boostNftTokenAccount,
+      boostNfnodeEntryPDA,
+      mint,
+      { byod: {} }
+    );
+    await depositTokens(
+      program,
+      userKeypair,
+      mint,
+      boostNftMint,
+      boostNftTokenAccount,
+      new anchor.BN(1),
+      { days365: {} }
+    );
+
+    await ownerClaimRewards(
+      program,
+      provider,
+      adminKeypair,
+      userKeypair,
+      mint,
+      boostNftMint,
+      boostNftTokenAccount,
+      new anchor.BN(100000000),
+      new anchor.BN(1),
+      null,
+      getAssociatedTokenAddressSync(mint, boostDepositStorage, true)
+    );
+    // the whole reward is compounded at 1.0x, not the 1.5x of the 365 day tier
+    const nfnodeEntryState = await program.account.nfNodeEntry.fetch(boostNfnodeEntryPDA);
+    expect(nfnodeEntryState.depositAmount.toNumber()).to.equal(1 + 100000000);
+
+    await setNfnodeCollection(program, adminKeypair, null);
+  });
 });
\ No newline at end of file
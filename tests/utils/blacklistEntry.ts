@@ -0,0 +1,25 @@
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { PublicKey } from "@solana/web3.js";
+import { getAccount } from "@solana/spl-token";
+
+export async function getRecipientBlacklistEntryPDA(
+  program: Program<RewardSystem>,
+  recipientTokenAccount: PublicKey | null
+) {
+  // only routed payouts pass a recipient token account
+  if (recipientTokenAccount === null) {
+    return null;
+  }
+  const { owner } = await getAccount(
+    program.provider.connection,
+    recipientTokenAccount,
+    "confirmed"
+  );
+  const [blacklistEntryPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("blacklist"), owner.toBuffer()],
+    program.programId
+  );
+
+  return blacklistEntryPDA;
+}
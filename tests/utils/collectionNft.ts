@@ -0,0 +1,171 @@
+import {
+  AuthorityType,
+  createInitializeGroupMemberPointerInstruction,
+  createInitializeGroupPointerInstruction,
+  createInitializeMetadataPointerInstruction,
+  createInitializeMintInstruction,
+  ExtensionType,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  setAuthority,
+  tokenGroupInitializeGroupWithRentTransfer,
+  tokenGroupMemberInitializeWithRentTransfer,
+  tokenMetadataInitializeWithRentTransfer,
+  tokenMetadataUpdateFieldWithRentTransfer,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+
+const confirmOptions = { commitment: "confirmed" as const };
+
+async function createMintWithExtensions(
+  connection: Connection,
+  payer: Keypair,
+  mintKeypair: Keypair,
+  extensions: ExtensionType[],
+  initializeExtensions: ReturnType<typeof createInitializeGroupPointerInstruction>[]
+) {
+  const mintLen = getMintLen(extensions);
+  const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+  const transaction = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: payer.publicKey,
+      newAccountPubkey: mintKeypair.publicKey,
+      space: mintLen,
+      lamports,
+      programId: TOKEN_2022_PROGRAM_ID,
+    }),
+    ...initializeExtensions,
+    createInitializeMintInstruction(
+      mintKeypair.publicKey,
+      0,
+      payer.publicKey,
+      null,
+      TOKEN_2022_PROGRAM_ID
+    )
+  );
+  await sendAndConfirmTransaction(connection, transaction, [payer, mintKeypair], confirmOptions);
+}
+
+// collection mint carrying the token-group extension
+export async function createCollectionMint(connection: Connection, payer: Keypair) {
+  const collectionKeypair = Keypair.generate();
+  await createMintWithExtensions(connection, payer, collectionKeypair, [ExtensionType.GroupPointer], [
+    createInitializeGroupPointerInstruction(
+      collectionKeypair.publicKey,
+      payer.publicKey,
+      collectionKeypair.publicKey,
+      TOKEN_2022_PROGRAM_ID
+    ),
+  ]);
+  await tokenGroupInitializeGroupWithRentTransfer(
+    connection,
+    payer,
+    collectionKeypair.publicKey,
+    payer,
+    payer.publicKey,
+    BigInt(1000),
+    [],
+    confirmOptions,
+    TOKEN_2022_PROGRAM_ID
+  );
+  return collectionKeypair.publicKey;
+}
+
+// nfnode nft minted into the collection with its device type, then its mint authority is revoked
+export async function createRevokedCollectionNft(
+  connection: Connection,
+  payer: Keypair,
+  owner: PublicKey,
+  collection: PublicKey,
+  deviceType: string
+) {
+  const nftKeypair = Keypair.generate();
+  const nftMint = nftKeypair.publicKey;
+  await createMintWithExtensions(
+    connection,
+    payer,
+    nftKeypair,
+    [ExtensionType.GroupMemberPointer, ExtensionType.MetadataPointer],
+    [
+      createInitializeGroupMemberPointerInstruction(
+        nftMint,
+        payer.publicKey,
+        nftMint,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMetadataPointerInstruction(
+        nftMint,
+        payer.publicKey,
+        nftMint,
+        TOKEN_2022_PROGRAM_ID
+      ),
+    ]
+  );
+  await tokenMetadataInitializeWithRentTransfer(
+    connection,
+    payer,
+    nftMint,
+    payer.publicKey,
+    payer,
+    "NfNode",
+    "NFNODE",
+    "",
+    [],
+    confirmOptions,
+    TOKEN_2022_PROGRAM_ID
+  );
+  await tokenMetadataUpdateFieldWithRentTransfer(
+    connection,
+    payer,
+    nftMint,
+    payer,
+    "device_type",
+    deviceType,
+    [],
+    confirmOptions,
+    TOKEN_2022_PROGRAM_ID
+  );
+  await tokenGroupMemberInitializeWithRentTransfer(
+    connection,
+    payer,
+    nftMint,
+    payer,
+    collection,
+    payer,
+    [],
+    confirmOptions,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const nftTokenAccount = await getOrCreateAssociatedTokenAccount(
+    connection,
+    payer,
+    nftMint,
+    owner,
+    false,
+    "confirmed",
+    confirmOptions,
+    TOKEN_2022_PROGRAM_ID
+  );
+  await mintTo(connection, payer, nftMint, nftTokenAccount.address, payer, 1, [], confirmOptions, TOKEN_2022_PROGRAM_ID);
+  await setAuthority(
+    connection,
+    payer,
+    nftMint,
+    payer,
+    AuthorityType.MintTokens,
+    null,
+    [],
+    confirmOptions,
+    TOKEN_2022_PROGRAM_ID
+  );
+  return { nftMint, nftTokenAccount: nftTokenAccount.address };
+}
@@ -0,0 +1,7 @@
+import { Keypair } from "@solana/web3.js";
+
+// the Metaplex Core asset loaded from tests/fixtures/core-asset.json, the test validator has no
+// Core program so the asset is a fixed account owned by this keypair and in this collection
+export const CORE_ASSET_OWNER = Keypair.fromSeed(new Uint8Array(32).fill(7));
+export const CORE_ASSET = Keypair.fromSeed(new Uint8Array(32).fill(8)).publicKey;
+export const CORE_COLLECTION = Keypair.fromSeed(new Uint8Array(32).fill(9)).publicKey;
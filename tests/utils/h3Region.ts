@@ -0,0 +1,45 @@
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { PublicKey } from "@solana/web3.js";
+
+// resolution region multipliers are configured at, must match H3_REGION_RESOLUTION
+export const H3_REGION_RESOLUTION = 5n;
+
+export function h3Parent(h3Cell: bigint, resolution: bigint) {
+  let parent = (h3Cell & ~(0xfn << 52n)) | (resolution << 52n);
+  for (let digit = resolution + 1n; digit <= 15n; digit++) {
+    parent |= 7n << ((15n - digit) * 3n);
+  }
+  return parent;
+}
+
+export function getH3RegionAddress(program: Program<RewardSystem>, regionCell: bigint) {
+  const regionCellBuffer = Buffer.alloc(8);
+  regionCellBuffer.writeBigUInt64LE(regionCell);
+  const [h3RegionPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("h3_region"), regionCellBuffer],
+    program.programId
+  );
+
+  return h3RegionPDA;
+}
+
+// region account of the nfnode location, null while no location is asserted
+export async function getH3RegionPDA(
+  program: Program<RewardSystem>,
+  nftMint: PublicKey
+) {
+  const [nfnodeLocationPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("nfnode_location"), nftMint.toBuffer()],
+    program.programId
+  );
+  const nfnodeLocation = await program.account.nfNodeLocation.fetchNullable(nfnodeLocationPDA);
+  if (!nfnodeLocation) {
+    return null;
+  }
+
+  return getH3RegionAddress(
+    program,
+    h3Parent(BigInt(nfnodeLocation.h3Cell.toString()), H3_REGION_RESOLUTION)
+  );
+}
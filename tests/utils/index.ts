@@ -1,4 +1,9 @@
 export * from "./airdrop";
 export * from "./generatePDA";
 export * from "./getKeypair";
-export * from "./setup";
\ No newline at end of file
+export * from "./setup";
+export * from "./mintAuthorityEntry";
+export * from "./h3Region";
+export * from "./collectionNft";
+export * from "./coreAsset";
+export * from "./blacklistEntry";
\ No newline at end of file
@@ -0,0 +1,26 @@
+import { Program } from "@coral-xyz/anchor";
+import { RewardSystem } from "../../target/types/reward_system";
+import { PublicKey } from "@solana/web3.js";
+import { getMint, TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+export async function getMintAuthorityEntryPDA(
+  program: Program<RewardSystem>,
+  nftMint: PublicKey
+) {
+  const mintInfo = await getMint(
+    program.provider.connection,
+    nftMint,
+    "confirmed",
+    TOKEN_2022_PROGRAM_ID
+  );
+  // nfts whose mint authority was revoked are authorized through the nfnode collection instead
+  if (mintInfo.mintAuthority === null) {
+    return null;
+  }
+  const [mintAuthorityEntryPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("mint_authority"), mintInfo.mintAuthority.toBuffer()],
+    program.programId
+  );
+
+  return mintAuthorityEntryPDA;
+}